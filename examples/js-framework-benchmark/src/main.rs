@@ -187,5 +187,7 @@ fn main() {
     #[cfg(debug_assertions)]
     console_error_panic_hook::set_once();
 
+    // Use a single listener on the root instead of one per row.
+    sycamore::web::events::set_delegation(true);
     sycamore::render_to(App, &mount_el);
 }
@@ -1,5 +1,5 @@
 use sycamore::prelude::*;
-use sycamore::web::Suspense;
+use sycamore::web::{Suspense, Title};
 
 async fn sleep_ms(ms: u64) {
     is_ssr! {
@@ -41,9 +41,10 @@ fn CounterButton() -> View {
 }
 
 #[component]
-fn App() -> View {
+pub fn App() -> View {
     let delays = [1000, 2000, 1000];
     view! {
+        Title(text="SSR Streaming Demo")
         p {
             strong { "SSR Streaming Demo" }
         }
@@ -105,23 +106,3 @@ fn App() -> View {
         }
     }
 }
-
-#[component]
-pub fn Main() -> View {
-    view! {
-        html {
-            head {
-                sycamore::web::HydrationScript {}
-                sycamore::web::NoHydrate {
-                    link(rel="preload", href="/dist/ssr-streaming_bg.wasm", r#as="fetch", crossorigin="")
-                    script(r#type="module") {
-                        "import init from '/dist/ssr-streaming.js'; const wasm = await init({});"
-                    }
-                }
-            }
-            body {
-                App {}
-            }
-        }
-    }
-}
@@ -16,6 +16,5 @@ async fn main() {
 fn main() {
     console_error_panic_hook::set_once();
 
-    let document = document();
-    sycamore::hydrate_to(app::Main, &document);
+    sycamore::hydrate(app::App);
 }
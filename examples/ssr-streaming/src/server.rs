@@ -3,11 +3,18 @@ use axum::http::header;
 use axum::response::IntoResponse;
 use axum::routing::get;
 use axum::Router;
+use sycamore::web::DocumentShell;
 use tokio::runtime::Handle;
 use tokio::task::LocalSet;
 use tokio_stream::StreamExt;
 use tower_http::services::ServeDir;
 
+fn shell() -> DocumentShell {
+    DocumentShell::new()
+        .lang("en")
+        .wasm("/dist/ssr-streaming.js", "/dist/ssr-streaming_bg.wasm")
+}
+
 async fn root() -> impl IntoResponse {
     let (tx, rx) = tokio::sync::oneshot::channel();
     tokio::task::spawn_blocking(|| {
@@ -15,7 +22,7 @@ async fn root() -> impl IntoResponse {
         handle.block_on(async move {
             let local = LocalSet::new();
             local.spawn_local(async move {
-                tx.send(sycamore::render_to_string_stream(crate::app::Main))
+                tx.send(sycamore::render_document_stream(&shell(), crate::app::App))
                     .ok()
                     .expect("reciever is alive");
             });
@@ -34,7 +41,7 @@ async fn root_blocking() -> impl IntoResponse {
     tokio::task::spawn_blocking(|| {
         let handle = Handle::current();
         handle.block_on(async move {
-            tx.send(sycamore::render_to_string_await_suspense(crate::app::Main).await)
+            tx.send(sycamore::render_document_await_suspense(&shell(), crate::app::App).await)
                 .ok()
                 .unwrap();
         });
@@ -1,5 +1,6 @@
 //! Utilities for components and component properties.
 
+use std::cell::RefCell;
 use std::fmt;
 
 use sycamore_reactive::*;
@@ -12,6 +13,42 @@ pub fn component_scope<T>(f: impl FnOnce() -> T) -> T {
     untrack(f)
 }
 
+thread_local! {
+    /// The names of the components that are currently being created, from outermost to
+    /// innermost.
+    static COMPONENT_PATH: RefCell<Vec<&'static str>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Like [`component_scope`] but also records the `name` of the component in the
+/// [`component_path`] while `f` is running. The name is only recorded in debug builds.
+#[doc(hidden)]
+pub fn named_component_scope<T>(name: &'static str, f: impl FnOnce() -> T) -> T {
+    if !cfg!(debug_assertions) {
+        return component_scope(f);
+    }
+
+    /// Pops the name again, even if `f` panics.
+    struct PopOnDrop;
+    impl Drop for PopOnDrop {
+        fn drop(&mut self) {
+            COMPONENT_PATH.with(|path| path.borrow_mut().pop());
+        }
+    }
+
+    COMPONENT_PATH.with(|path| path.borrow_mut().push(name));
+    let _guard = PopOnDrop;
+    component_scope(f)
+}
+
+/// Returns the names of the components that are currently being created, from outermost to
+/// innermost. This is useful for diagnostics.
+///
+/// Only components created with the `view!` macro are included. The path is always empty in
+/// release builds.
+pub fn component_path() -> Vec<&'static str> {
+    COMPONENT_PATH.with(|path| path.borrow().clone())
+}
+
 /// A trait that is implemented automatically by the `Props` derive macro.
 ///
 /// This is used when constructing components in the `view!` macro.
@@ -0,0 +1,347 @@
+//! Actions for running tracked async mutations.
+//!
+//! Unlike resources which are used for loading data, actions are used for performing side effects
+//! such as submitting a form. An action is only run when it is explicitly dispatched.
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::panic::Location;
+
+use futures::future::{abortable, AbortHandle, LocalBoxFuture};
+use futures::{Future, FutureExt};
+use sycamore_reactive::*;
+
+use crate::*;
+
+/// Determines what happens when an async task is started while a previous one is still running.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ConcurrencyPolicy {
+    /// Run all tasks concurrently. Results are applied in the order in which the tasks complete.
+    #[default]
+    Concurrent,
+    /// Wait for the running task to complete before starting the next one. Tasks are run in the
+    /// order in which they were started.
+    Queue,
+    /// Abort the running task and start the new one immediately.
+    CancelPrevious,
+    /// Do not start the new task if there is a task that is still running.
+    IgnoreWhilePending,
+}
+
+/// A tracked async mutation. Created using [`create_action`].
+pub struct Action<I: 'static, T: 'static, E: 'static> {
+    /// The value of the last successful run.
+    value: Signal<Option<T>>,
+    /// The error of the last run, if it failed.
+    error: Signal<Option<E>>,
+    /// The number of runs that are currently in progress.
+    pending: Signal<u32>,
+    /// The number of runs that have completed, successfully or not.
+    version: Signal<u32>,
+    /// The async function that is called when the action is dispatched.
+    #[allow(clippy::complexity)]
+    f: Signal<Box<dyn FnMut(I) -> LocalBoxFuture<'static, Result<T, E>>>>,
+    /// Inputs that are waiting to be run when using [`ConcurrencyPolicy::Queue`].
+    queue: Signal<VecDeque<I>>,
+    /// Abort handles for all the runs that are currently in progress.
+    abort_handles: Signal<Vec<AbortHandle>>,
+    policy: ConcurrencyPolicy,
+    /// The suspense scope that should be suspended while the action is pending.
+    suspense_scope: Option<SuspenseScope>,
+    /// Where the action was created. Used for suspense task introspection.
+    location: &'static Location<'static>,
+}
+
+impl<I, T, E> Clone for Action<I, T, E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<I, T, E> Copy for Action<I, T, E> {}
+
+impl<I, T, E> Action<I, T, E> {
+    /// Sets the [`ConcurrencyPolicy`] for when the action is dispatched while it is still pending.
+    ///
+    /// By default, this is [`ConcurrencyPolicy::Concurrent`].
+    pub fn with_policy(mut self, policy: ConcurrencyPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Suspends the current suspense scope while the action is pending.
+    ///
+    /// This is mostly useful inside of a `Transition`, which keeps showing the previous content
+    /// while the action is pending.
+    pub fn with_suspense(mut self) -> Self {
+        self.suspense_scope = try_use_context::<SuspenseScope>();
+        self
+    }
+
+    /// Runs the action with `input`, taking the [`ConcurrencyPolicy`] into account.
+    pub fn dispatch(self, input: I) {
+        let is_pending = self.pending.get_untracked() > 0;
+        match self.policy {
+            ConcurrencyPolicy::Concurrent => {}
+            ConcurrencyPolicy::Queue if is_pending => {
+                self.queue.update_silent(|queue| queue.push_back(input));
+                return;
+            }
+            ConcurrencyPolicy::Queue => {}
+            ConcurrencyPolicy::CancelPrevious => self.cancel(),
+            ConcurrencyPolicy::IgnoreWhilePending if is_pending => return,
+            ConcurrencyPolicy::IgnoreWhilePending => {}
+        }
+        self.run(input);
+    }
+
+    /// Aborts all the runs that are currently in progress and clears the queue.
+    pub fn cancel(self) {
+        self.queue.update_silent(|queue| queue.clear());
+        for handle in self.abort_handles.update_silent(std::mem::take) {
+            handle.abort();
+        }
+    }
+
+    fn run(mut self, input: I) {
+        let fut = self.f.update_silent(|f| f(input));
+        let (fut, handle) = abortable(fut);
+        self.abort_handles
+            .update_silent(|handles| handles.push(handle.clone()));
+        let guard = self.suspense_scope.map(|scope| {
+            SuspenseTaskGuard::from_scope(scope)
+                .with_label("Action")
+                .with_location(self.location)
+        });
+        self.pending += 1;
+
+        spawn_local_scoped(async move {
+            let result = fut.await;
+            batch(move || {
+                if let Ok(result) = result {
+                    match result {
+                        Ok(value) => {
+                            self.value.set(Some(value));
+                            self.error.set(None);
+                        }
+                        Err(err) => self.error.set(Some(err)),
+                    }
+                    self.version += 1;
+                }
+                self.pending -= 1;
+                drop(guard);
+            });
+            // Remove the handle of this run. Marking it as aborted lets us find it without needing
+            // to compare handles.
+            handle.abort();
+            self.abort_handles
+                .update_silent(|handles| handles.retain(|h| !h.is_aborted()));
+
+            if let Some(next) = self.queue.update_silent(|queue| queue.pop_front()) {
+                self.run(next);
+            }
+        });
+    }
+
+    /// Returns whether the action is currently running. This is reactive.
+    pub fn pending(self) -> bool {
+        self.pending.get() > 0
+    }
+
+    /// The number of times the action has completed, successfully or not. This is reactive.
+    ///
+    /// This is useful for triggering something after every completed run, even if the value is
+    /// the same.
+    pub fn version(self) -> u32 {
+        self.version.get()
+    }
+
+    /// The value of the last successful run, or `None` if the action has never completed
+    /// successfully.
+    pub fn value(self) -> ReadSignal<Option<T>> {
+        *self.value
+    }
+
+    /// The error of the last run, or `None` if the last run completed successfully.
+    ///
+    /// Note that the value from the last successful run is kept when a run fails.
+    pub fn error(self) -> ReadSignal<Option<E>> {
+        *self.error
+    }
+}
+
+impl<I, T, E> fmt::Debug for Action<I, T, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Action")
+            .field("pending", &self.pending.get_untracked())
+            .field("version", &self.version.get_untracked())
+            .field("policy", &self.policy)
+            .finish()
+    }
+}
+
+/// Creates a new [`Action`] that runs `f` whenever it is dispatched.
+///
+/// The future returned by `f` is spawned using [`spawn_local_scoped`] and is therefore aborted if
+/// the current scope is destroyed.
+///
+/// # Example
+/// ```
+/// # use sycamore_futures::*;
+/// # use sycamore_reactive::*;
+/// # let _ = create_root(|| {
+/// let save = create_action(|name: String| async move {
+///     // Send the name to the server...
+///     if name.is_empty() {
+///         Err("name cannot be empty")
+///     } else {
+///         Ok(name)
+///     }
+/// })
+/// .with_policy(ConcurrencyPolicy::IgnoreWhilePending);
+///
+/// // Dispatch the action, for example when a form is submitted.
+/// let on_submit = move || save.dispatch("Sycamore".to_string());
+/// # });
+/// ```
+#[track_caller]
+pub fn create_action<I, T, E, F, Fut>(mut f: F) -> Action<I, T, E>
+where
+    F: FnMut(I) -> Fut + 'static,
+    Fut: Future<Output = Result<T, E>> + 'static,
+{
+    Action {
+        value: create_signal(None),
+        error: create_signal(None),
+        pending: create_signal(0),
+        version: create_signal(0),
+        f: create_signal(Box::new(move |input| f(input).boxed_local())),
+        queue: create_signal(VecDeque::new()),
+        abort_handles: create_signal(Vec::new()),
+        policy: ConcurrencyPolicy::default(),
+        suspense_scope: None,
+        location: Location::caller(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    /// Creates an action that sleeps for `ms` milliseconds and then returns the input, or an error
+    /// if the input is negative.
+    fn delayed_action(policy: ConcurrencyPolicy) -> Action<i32, i32, String> {
+        create_action(|input: i32| async move {
+            sleep(Duration::from_millis(100)).await;
+            if input < 0 {
+                Err(format!("negative: {input}"))
+            } else {
+                Ok(input)
+            }
+        })
+        .with_policy(policy)
+    }
+
+    #[test]
+    fn dispatch_sets_value_and_error() {
+        with_test_executor(|executor| {
+            let action = delayed_action(ConcurrencyPolicy::Concurrent);
+            assert!(!action.pending());
+
+            action.dispatch(1);
+            executor.run_until_stalled();
+            assert!(action.pending());
+            assert_eq!(action.value().get(), None);
+
+            executor.advance_time(Duration::from_millis(100));
+            assert!(!action.pending());
+            assert_eq!(action.value().get(), Some(1));
+            assert_eq!(action.version(), 1);
+
+            action.dispatch(-1);
+            executor.advance_time(Duration::from_millis(100));
+            assert_eq!(
+                action.value().get(),
+                Some(1),
+                "previous value should be kept"
+            );
+            assert_eq!(action.error().get_clone(), Some("negative: -1".to_string()));
+            assert_eq!(action.version(), 2);
+
+            action.dispatch(2);
+            executor.advance_time(Duration::from_millis(100));
+            assert_eq!(action.value().get(), Some(2));
+            assert_eq!(action.error().get_clone(), None);
+        });
+    }
+
+    #[test]
+    fn queue_policy_runs_in_order() {
+        with_test_executor(|executor| {
+            let action = delayed_action(ConcurrencyPolicy::Queue);
+            let values = create_signal_history(move || action.value().get());
+            action.dispatch(1);
+            action.dispatch(2);
+            action.dispatch(3);
+
+            executor.advance_time(Duration::from_millis(100));
+            assert_eq!(values.take(), [None, Some(1)]);
+            assert!(action.pending());
+
+            executor.advance_time(Duration::from_millis(200));
+            assert_eq!(values.take(), [Some(2), Some(3)]);
+            assert!(!action.pending());
+            assert_eq!(action.version(), 3);
+        });
+    }
+
+    #[test]
+    fn cancel_previous_policy_aborts_running() {
+        with_test_executor(|executor| {
+            let action = delayed_action(ConcurrencyPolicy::CancelPrevious);
+            action.dispatch(1);
+            executor.advance_time(Duration::from_millis(50));
+            action.dispatch(2);
+
+            executor.advance_time(Duration::from_millis(50));
+            assert_eq!(action.value().get(), None);
+            assert!(action.pending());
+
+            executor.advance_time(Duration::from_millis(50));
+            assert_eq!(action.value().get(), Some(2));
+            assert_eq!(action.version(), 1);
+            assert!(!action.pending());
+        });
+    }
+
+    #[test]
+    fn ignore_while_pending_policy() {
+        with_test_executor(|executor| {
+            let action = delayed_action(ConcurrencyPolicy::IgnoreWhilePending);
+            action.dispatch(1);
+            action.dispatch(2);
+
+            executor.advance_time(Duration::from_millis(200));
+            assert_eq!(action.value().get(), Some(1));
+            assert_eq!(action.version(), 1);
+        });
+    }
+
+    #[test]
+    fn with_suspense_suspends_scope() {
+        with_test_executor(|executor| {
+            let (action, scope) = create_suspense_scope(|| {
+                delayed_action(ConcurrencyPolicy::Concurrent).with_suspense()
+            });
+            let is_loading = scope.is_loading();
+            assert!(!is_loading.get());
+
+            action.dispatch(1);
+            assert!(is_loading.get());
+
+            executor.advance_time(Duration::from_millis(100));
+            assert!(!is_loading.get());
+        });
+    }
+}
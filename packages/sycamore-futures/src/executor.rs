@@ -0,0 +1,418 @@
+//! Pluggable executors for running `!Send` futures.
+//!
+//! By default, futures are spawned on `tokio` (using a `tokio::task::LocalSet`) when not on
+//! `wasm32` and on `wasm-bindgen-futures` when on `wasm32`. This can be changed by implementing the
+//! [`Executor`] trait and either setting it as the global executor for the current thread with
+//! [`set_executor`] or by running a future with a specific executor using
+//! [`provide_executor_scope_with`].
+
+use std::cell::RefCell;
+use std::fmt;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use futures::future::LocalBoxFuture;
+use futures::{Future, FutureExt};
+use pin_project::pin_project;
+
+/// An executor that can run `!Send` futures.
+///
+/// Implement this trait to run Sycamore on a different async runtime, such as `smol`,
+/// `async-executor` or a custom single-threaded executor.
+pub trait Executor {
+    /// Spawns a `!Send` future onto the executor. The future should be polled to completion in the
+    /// background.
+    fn spawn_local(&self, fut: LocalBoxFuture<'static, ()>);
+
+    /// Runs `fut` to completion while providing whatever environment is needed for
+    /// [`Executor::spawn_local`] to work.
+    ///
+    /// For example, the `tokio` executor runs `fut` inside a new `tokio::task::LocalSet`. By
+    /// default, this simply returns `fut` unchanged.
+    fn run_until<'a>(&self, fut: LocalBoxFuture<'a, ()>) -> LocalBoxFuture<'a, ()> {
+        fut
+    }
+
+    /// Returns a future that resolves once `duration` has elapsed.
+    ///
+    /// By default, this uses `setTimeout` on `wasm32` and a single background timer thread
+    /// otherwise. Executors that come with their own timers should override this.
+    fn sleep(&self, duration: Duration) -> LocalBoxFuture<'static, ()> {
+        platform_sleep(duration)
+    }
+
+    /// Returns the current time, measured from some fixed point in the past.
+    ///
+    /// This is only meaningful when compared with other values returned from the same executor.
+    fn now(&self) -> Duration {
+        platform_now()
+    }
+}
+
+/// The default executor when not on `wasm32`. Uses `tokio::task::spawn_local`.
+///
+/// Futures can only be spawned inside a `tokio::task::LocalSet`, which is created automatically by
+/// [`provide_executor_scope`](crate::provide_executor_scope).
+///
+/// [`sleep`](crate::sleep) uses `tokio::time::sleep`, which panics if the `tokio` runtime was built
+/// without the time driver. Make sure to call `enable_time` (or `enable_all`) when building the
+/// runtime, or use a different [`Executor`] if that is not possible.
+#[cfg(any(not(target_arch = "wasm32"), sycamore_force_ssr))]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct TokioExecutor;
+
+#[cfg(any(not(target_arch = "wasm32"), sycamore_force_ssr))]
+impl Executor for TokioExecutor {
+    fn spawn_local(&self, fut: LocalBoxFuture<'static, ()>) {
+        tokio::task::spawn_local(fut);
+    }
+
+    fn run_until<'a>(&self, fut: LocalBoxFuture<'a, ()>) -> LocalBoxFuture<'a, ()> {
+        async move {
+            let local = tokio::task::LocalSet::new();
+            local.run_until(fut).await;
+        }
+        .boxed_local()
+    }
+
+    fn sleep(&self, duration: Duration) -> LocalBoxFuture<'static, ()> {
+        tokio::time::sleep(duration).boxed_local()
+    }
+}
+
+/// The default executor when on `wasm32`. Uses `wasm_bindgen_futures::spawn_local`.
+#[cfg(all(target_arch = "wasm32", not(sycamore_force_ssr)))]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct WasmBindgenExecutor;
+
+#[cfg(all(target_arch = "wasm32", not(sycamore_force_ssr)))]
+impl Executor for WasmBindgenExecutor {
+    fn spawn_local(&self, fut: LocalBoxFuture<'static, ()>) {
+        wasm_bindgen_futures::spawn_local(fut);
+    }
+}
+
+/// The default executor for the current target.
+#[cfg(any(not(target_arch = "wasm32"), sycamore_force_ssr))]
+pub type DefaultExecutor = TokioExecutor;
+/// The default executor for the current target.
+#[cfg(all(target_arch = "wasm32", not(sycamore_force_ssr)))]
+pub type DefaultExecutor = WasmBindgenExecutor;
+
+/// Default implementation for [`Executor::sleep`].
+///
+/// All the timers are fired from a single background thread, which is spawned the first time this
+/// is called.
+#[cfg(any(not(target_arch = "wasm32"), sycamore_force_ssr))]
+fn platform_sleep(duration: Duration) -> LocalBoxFuture<'static, ()> {
+    use std::cmp::Ordering;
+    use std::collections::BinaryHeap;
+    use std::sync::mpsc;
+    use std::sync::OnceLock;
+    use std::time::Instant;
+
+    use futures::channel::oneshot;
+
+    struct Timer {
+        deadline: Instant,
+        tx: oneshot::Sender<()>,
+    }
+
+    // Ordered by deadline in reverse, so that the `BinaryHeap` pops the earliest timer first.
+    impl PartialEq for Timer {
+        fn eq(&self, other: &Self) -> bool {
+            self.deadline == other.deadline
+        }
+    }
+    impl Eq for Timer {}
+    impl PartialOrd for Timer {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+    impl Ord for Timer {
+        fn cmp(&self, other: &Self) -> Ordering {
+            other.deadline.cmp(&self.deadline)
+        }
+    }
+
+    fn run_timers(rx: mpsc::Receiver<Timer>) {
+        let mut timers = BinaryHeap::<Timer>::new();
+        loop {
+            let now = Instant::now();
+            while timers.peek().is_some_and(|timer| timer.deadline <= now) {
+                let _ = timers.pop().unwrap().tx.send(());
+            }
+            let timer = match timers.peek() {
+                Some(next) => match rx.recv_timeout(next.deadline - now) {
+                    Ok(timer) => timer,
+                    Err(mpsc::RecvTimeoutError::Timeout) => continue,
+                    Err(mpsc::RecvTimeoutError::Disconnected) => return,
+                },
+                None => match rx.recv() {
+                    Ok(timer) => timer,
+                    Err(_) => return,
+                },
+            };
+            timers.push(timer);
+        }
+    }
+
+    static TIMERS: OnceLock<mpsc::Sender<Timer>> = OnceLock::new();
+
+    let (tx, rx) = oneshot::channel();
+    let timer = Timer {
+        deadline: Instant::now() + duration,
+        tx,
+    };
+    TIMERS
+        .get_or_init(|| {
+            let (tx, rx) = mpsc::channel();
+            std::thread::Builder::new()
+                .name("sycamore-timer".to_string())
+                .spawn(move || run_timers(rx))
+                .expect("could not spawn timer thread");
+            tx
+        })
+        .send(timer)
+        .expect("timer thread should be running");
+    async move {
+        let _ = rx.await;
+    }
+    .boxed_local()
+}
+
+/// Default implementation for [`Executor::sleep`].
+#[cfg(all(target_arch = "wasm32", not(sycamore_force_ssr)))]
+fn platform_sleep(duration: Duration) -> LocalBoxFuture<'static, ()> {
+    use wasm_bindgen::prelude::*;
+
+    #[wasm_bindgen]
+    extern "C" {
+        #[wasm_bindgen(js_name = "setTimeout")]
+        fn set_timeout(f: &js_sys::Function, ms: f64) -> JsValue;
+    }
+
+    let promise = js_sys::Promise::new(&mut |resolve, _reject| {
+        set_timeout(&resolve, duration.as_secs_f64() * 1000.0);
+    });
+    async move {
+        let _ = wasm_bindgen_futures::JsFuture::from(promise).await;
+    }
+    .boxed_local()
+}
+
+/// Default implementation for [`Executor::now`].
+#[cfg(any(not(target_arch = "wasm32"), sycamore_force_ssr))]
+fn platform_now() -> Duration {
+    use std::time::Instant;
+
+    thread_local! {
+        static START: Instant = Instant::now();
+    }
+    START.with(Instant::elapsed)
+}
+
+/// Default implementation for [`Executor::now`].
+#[cfg(all(target_arch = "wasm32", not(sycamore_force_ssr)))]
+fn platform_now() -> Duration {
+    use wasm_bindgen::prelude::*;
+
+    #[wasm_bindgen]
+    extern "C" {
+        // `performance.now()` is monotonic, unlike `Date.now()`.
+        #[wasm_bindgen(js_namespace = performance, js_name = "now")]
+        fn performance_now() -> f64;
+    }
+
+    Duration::from_secs_f64(performance_now() / 1000.0)
+}
+
+thread_local! {
+    /// The executor that is used if no executor is set using [`provide_executor_scope_with`].
+    static GLOBAL_EXECUTOR: RefCell<Option<Rc<dyn Executor>>> = const { RefCell::new(None) };
+    /// The executor set by [`provide_executor_scope_with`]. This takes precedence over the global
+    /// executor.
+    static SCOPED_EXECUTOR: RefCell<Option<Rc<dyn Executor>>> = const { RefCell::new(None) };
+}
+
+/// Sets the global executor for the current thread.
+///
+/// All futures spawned with [`spawn_local`](crate::spawn_local) or
+/// [`spawn_local_scoped`](crate::spawn_local_scoped) will be spawned onto this executor, unless
+/// they are spawned inside [`provide_executor_scope_with`].
+///
+/// This should generally be called once at the start of the program, before anything is rendered.
+pub fn set_executor(executor: impl Executor + 'static) {
+    GLOBAL_EXECUTOR.with(|global| *global.borrow_mut() = Some(Rc::new(executor)));
+}
+
+/// Returns the executor that is currently being used for spawning futures.
+///
+/// This is the executor provided by [`provide_executor_scope_with`] if inside one, or otherwise
+/// the global executor set with [`set_executor`]. If neither is set, returns the
+/// [`DefaultExecutor`].
+pub fn use_executor() -> Rc<dyn Executor> {
+    if let Some(executor) = SCOPED_EXECUTOR.with(|scoped| scoped.borrow().clone()) {
+        return executor;
+    }
+    GLOBAL_EXECUTOR.with(|global| {
+        global
+            .borrow_mut()
+            .get_or_insert_with(|| Rc::new(DefaultExecutor::default()))
+            .clone()
+    })
+}
+
+/// Returns the executor provided by [`provide_executor_scope_with`], if any.
+pub(crate) fn scoped_executor() -> Option<Rc<dyn Executor>> {
+    SCOPED_EXECUTOR.with(|scoped| scoped.borrow().clone())
+}
+
+/// Returns a future that resolves once `duration` has elapsed according to the current
+/// [`Executor`].
+///
+/// Prefer this over runtime specific timers so that time can be controlled in tests using
+/// `TestExecutor`, which is available with the `test-utils` feature.
+pub fn sleep(duration: Duration) -> impl Future<Output = ()> {
+    use_executor().sleep(duration)
+}
+
+/// Returns the current time according to the current [`Executor`], measured from some fixed
+/// point in the past.
+pub fn now() -> Duration {
+    use_executor().now()
+}
+
+/// Runs `fut` to completion using `executor` for all futures that are spawned inside of it.
+///
+/// Unlike [`set_executor`], this only affects futures that are spawned while `fut` (or any future
+/// spawned by it) is being polled.
+pub async fn provide_executor_scope_with<U>(
+    executor: impl Executor + 'static,
+    fut: impl Future<Output = U>,
+) -> U {
+    let executor: Rc<dyn Executor> = Rc::new(executor);
+    let mut ret = None;
+    let fut = WithExecutor::new(Rc::clone(&executor), async {
+        ret = Some(fut.await);
+    });
+    executor.run_until(fut.boxed_local()).await;
+    ret.expect("future should have completed")
+}
+
+/// A future that sets the scoped executor while it is being polled.
+#[pin_project]
+pub(crate) struct WithExecutor<F> {
+    #[pin]
+    fut: F,
+    executor: Rc<dyn Executor>,
+}
+
+impl<F> WithExecutor<F> {
+    pub fn new(executor: Rc<dyn Executor>, fut: F) -> Self {
+        Self { fut, executor }
+    }
+}
+
+impl<F: Future> Future for WithExecutor<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        with_scoped_executor(Rc::clone(this.executor), || this.fut.poll(cx))
+    }
+}
+
+/// Runs `f` with `executor` set as the scoped executor, restoring the previous one afterwards.
+pub(crate) fn with_scoped_executor<U>(executor: Rc<dyn Executor>, f: impl FnOnce() -> U) -> U {
+    /// Restores the previous scoped executor even if `f` panics.
+    struct Restore(Option<Rc<dyn Executor>>);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            SCOPED_EXECUTOR.with(|scoped| *scoped.borrow_mut() = self.0.take());
+        }
+    }
+
+    let prev = SCOPED_EXECUTOR.with(|scoped| scoped.borrow_mut().replace(executor));
+    let _restore = Restore(prev);
+    f()
+}
+
+impl<F> fmt::Debug for WithExecutor<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WithExecutor").finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use futures::executor::{LocalPool, LocalSpawner};
+    use futures::task::LocalSpawnExt;
+
+    use super::*;
+    use crate::spawn_local;
+
+    /// An executor backed by `futures::executor::LocalPool`.
+    struct PoolExecutor {
+        spawner: LocalSpawner,
+        spawned: Rc<Cell<u32>>,
+    }
+
+    impl Executor for PoolExecutor {
+        fn spawn_local(&self, fut: LocalBoxFuture<'static, ()>) {
+            self.spawned.set(self.spawned.get() + 1);
+            self.spawner.spawn_local(fut).unwrap();
+        }
+    }
+
+    #[test]
+    fn custom_executor_is_used_in_scope() {
+        let mut pool = LocalPool::new();
+        let spawned = Rc::new(Cell::new(0));
+        let completed = Rc::new(Cell::new(0));
+
+        let executor = PoolExecutor {
+            spawner: pool.spawner(),
+            spawned: Rc::clone(&spawned),
+        };
+        pool.run_until(provide_executor_scope_with(executor, {
+            let completed = Rc::clone(&completed);
+            async move {
+                spawn_local(async move {
+                    completed.set(completed.get() + 1);
+                    // Nested spawns should use the same executor.
+                    spawn_local(async move {
+                        completed.set(completed.get() + 1);
+                    });
+                });
+            }
+        }));
+        pool.run_until_stalled();
+
+        assert_eq!(spawned.get(), 2);
+        assert_eq!(completed.get(), 2);
+        assert!(scoped_executor().is_none());
+    }
+
+    #[test]
+    fn default_sleep_fires_timers_in_order() {
+        let order = Rc::new(RefCell::new(Vec::new()));
+        let sleep_then_push = |ms, order: Rc<RefCell<Vec<u64>>>| async move {
+            platform_sleep(Duration::from_millis(ms)).await;
+            order.borrow_mut().push(ms);
+        };
+        let start = platform_now();
+        futures::executor::block_on(futures::future::join3(
+            sleep_then_push(30, Rc::clone(&order)),
+            sleep_then_push(10, Rc::clone(&order)),
+            sleep_then_push(20, Rc::clone(&order)),
+        ));
+        assert!(platform_now() - start >= Duration::from_millis(30));
+        assert_eq!(*order.borrow(), [10, 20, 30]);
+    }
+}
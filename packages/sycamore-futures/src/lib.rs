@@ -3,42 +3,60 @@
 #![deny(missing_debug_implementations)]
 #![warn(missing_docs)]
 
+mod action;
+mod executor;
 mod suspense;
+#[cfg(any(test, feature = "test-utils"))]
+mod test_executor;
+mod transition;
 
 use std::pin::Pin;
+use std::rc::Rc;
 use std::task::{Context, Poll};
 
 use futures::future::abortable;
 use futures::stream::Abortable;
-use futures::Future;
+use futures::{Future, FutureExt};
 use pin_project::pin_project;
 use sycamore_reactive::{on_cleanup, use_current_scope, NodeHandle};
 
+pub use self::action::*;
+pub use self::executor::*;
 pub use self::suspense::*;
+#[cfg(any(test, feature = "test-utils"))]
+pub use self::test_executor::*;
+pub use self::transition::*;
 
-/// If running on `wasm32` target, does nothing. Otherwise creates a new `tokio::task::LocalSet`
-/// scope.
+/// Runs the future inside the environment required by the current [`Executor`].
+///
+/// With the default executors, this does nothing if running on `wasm32` target. Otherwise creates
+/// a new `tokio::task::LocalSet` scope.
 pub async fn provide_executor_scope<U>(fut: impl Future<Output = U>) -> U {
-    #[cfg(all(target_arch = "wasm32", not(sycamore_force_ssr)))]
-    {
-        fut.await
-    }
-    #[cfg(any(not(target_arch = "wasm32"), sycamore_force_ssr))]
-    {
-        let local = tokio::task::LocalSet::new();
-        local.run_until(fut).await
-    }
+    let mut ret = None;
+    use_executor()
+        .run_until(
+            async {
+                ret = Some(fut.await);
+            }
+            .boxed_local(),
+        )
+        .await;
+    ret.expect("future should have completed")
 }
 
-/// Spawns a `!Send` future.
+/// Spawns a `!Send` future on the current [`Executor`].
 ///
 /// This will not auto cancel the task if the scope in which it is created is destroyed.
 /// For this purpose, use [`spawn_local_scoped`] instead.
 pub fn spawn_local(fut: impl Future<Output = ()> + 'static) {
-    #[cfg(any(not(target_arch = "wasm32"), sycamore_force_ssr))]
-    tokio::task::spawn_local(fut);
-    #[cfg(all(target_arch = "wasm32", not(sycamore_force_ssr)))]
-    wasm_bindgen_futures::spawn_local(fut);
+    // If we are inside `provide_executor_scope_with`, make sure that the spawned future also spawns
+    // its own futures onto the same executor.
+    if let Some(executor) = scoped_executor() {
+        let fut = WithExecutor::new(Rc::clone(&executor), fut);
+        executor.spawn_local(fut.boxed_local());
+    } else {
+        use_executor().spawn_local(fut.boxed_local());
+    }
 }
 
 /// Spawns a `!Send` future on the current scope.
@@ -3,30 +3,55 @@
 //! The [`Suspense`] component is used to "suspend" execution and wait until async tasks are
 //! finished before rendering.
 
+use std::borrow::Cow;
+use std::cell::Cell;
+use std::fmt::Write;
+use std::panic::Location;
+use std::time::Duration;
+
 use futures::channel::oneshot;
 use futures::Future;
 use sycamore_reactive::*;
 
 use crate::*;
 
+thread_local! {
+    /// Counter for the ids of suspense scopes and tasks.
+    static NEXT_ID: Cell<u64> = const { Cell::new(0) };
+}
+
+fn next_id() -> u64 {
+    let id = NEXT_ID.get();
+    NEXT_ID.set(id + 1);
+    id
+}
+
 /// A context value that keeps track of all the signals representing the number of tasks remaining
 /// in a suspense scope.
 ///
 /// This is useful for figuring out when all suspense tasks are completed on the page.
 #[derive(Copy, Clone, Debug, Default)]
 struct AllTasksRemaining {
-    all_tasks_remaining: Signal<Vec<Signal<u32>>>,
+    all_tasks_remaining: Signal<Vec<SuspenseScope>>,
 }
 
 /// Represents a new suspense scope. This is created by a call to [`create_suspense_scope`].
 #[derive(Copy, Clone, Debug)]
 pub struct SuspenseScope {
-    tasks_remaining: Signal<u32>,
+    id: u64,
+    /// The tasks in this scope that are not yet completed.
+    tasks: Signal<Vec<SuspenseTaskInfo>>,
+    /// The number of tasks that have been created in this scope since it was created.
+    tasks_started: Signal<u32>,
+    /// The child scopes of this scope that are still alive.
+    children: Signal<Vec<SuspenseScope>>,
     /// The parent suspense scope of the current scope, if it exists.
     pub parent: Option<Signal<SuspenseScope>>,
     /// Signal that is set to `true` when the view is rendered and streamed into the buffer.
     /// This is unused on the client side.
     pub sent: Signal<bool>,
+    /// Whether we have given up on waiting for this scope. See [`SuspenseScope::abandon`].
+    abandoned: Signal<bool>,
 }
 
 impl SuspenseScope {
@@ -35,42 +60,201 @@ impl SuspenseScope {
     /// The parent scope should always be located in a reactive scope that is an ancestor of
     /// this scope.
     pub fn new(parent: Option<SuspenseScope>) -> Self {
-        let tasks_remaining = create_signal(0);
         let global = use_global_scope().run_in(|| use_context_or_else(AllTasksRemaining::default));
-        global
-            .all_tasks_remaining
-            .update(|vec| vec.push(tasks_remaining));
-        // TODO: remove self from global if scope is disposed.
-        Self {
-            tasks_remaining,
+        let scope = Self {
+            id: next_id(),
+            tasks: create_signal(Vec::new()),
+            tasks_started: create_signal(0),
+            children: create_signal(Vec::new()),
             parent: parent.map(create_signal),
             sent: create_signal(false),
+            abandoned: create_signal(false),
+        };
+        global.all_tasks_remaining.update(|vec| vec.push(scope));
+        // TODO: remove self from global if scope is disposed.
+        if let Some(parent) = parent {
+            parent.children.update(|children| children.push(scope));
+            on_cleanup(move || {
+                if parent.children.is_alive() {
+                    parent
+                        .children
+                        .update(|children| children.retain(|child| child.id != scope.id));
+                }
+            });
         }
+        scope
+    }
+
+    /// Give up on waiting for this scope and all of its child scopes.
+    ///
+    /// Abandoned scopes are ignored by [`use_is_loading_global`]. This is used in SSR for
+    /// rendering the fallback of a suspense boundary that has timed out instead of waiting for
+    /// it forever.
+    pub fn abandon(self) {
+        self.abandoned.set(true);
+    }
+
+    /// Returns whether this scope or any of its parent scopes has been abandoned.
+    pub fn is_abandoned(self) -> bool {
+        self.abandoned.get()
+            || self
+                .parent
+                .as_ref()
+                .is_some_and(|parent| parent.get().is_abandoned())
     }
 
     /// Implementation for [`Self::is_loading`]. This is used to recursively check whether we are
     /// loading or not.
     fn _is_loading(self) -> bool {
-        self.tasks_remaining.get() > 0
+        self.tasks
+            .with(|tasks| tasks.iter().any(|task| !task.in_transition))
             || self
                 .parent
                 .as_ref()
                 .is_some_and(|parent| parent.get()._is_loading())
     }
 
+    /// Like [`Self::_is_loading`] but also includes the tasks that were created inside of a
+    /// transition.
+    fn _is_busy(self) -> bool {
+        self.has_own_tasks()
+            || self
+                .parent
+                .as_ref()
+                .is_some_and(|parent| parent.get()._is_busy())
+    }
+
     /// Returns a signal representing whether we are currently loading this suspense or not.
     ///
+    /// Tasks that were created inside of a transition are not included since they keep the
+    /// transition pending instead. See [`start_transition`].
+    ///
     /// Implementation for the [`use_is_loading`] hook.
     pub fn is_loading(self) -> ReadSignal<bool> {
         create_selector(move || self._is_loading())
     }
 
+    /// Whether there are tasks in this scope, not including child scopes, that are not yet
+    /// completed.
+    fn has_own_tasks(self) -> bool {
+        self.tasks.with(|tasks| !tasks.is_empty())
+    }
+
+    fn _pending_count(self) -> u32 {
+        self.tasks.with(Vec::len) as u32
+            + self.children.with(|children| {
+                children
+                    .iter()
+                    .map(|child| child._pending_count())
+                    .sum::<u32>()
+            })
+    }
+
+    fn _started_count(self) -> u32 {
+        self.tasks_started.get()
+            + self.children.with(|children| {
+                children
+                    .iter()
+                    .map(|child| child._started_count())
+                    .sum::<u32>()
+            })
+    }
+
+    fn collect_pending_tasks(self, buf: &mut Vec<SuspenseTaskInfo>) {
+        self.tasks.with(|tasks| buf.extend(tasks.iter().cloned()));
+        self.children.with(|children| {
+            for child in children {
+                child.collect_pending_tasks(buf);
+            }
+        });
+    }
+
+    /// Returns a signal with the number of tasks in this scope and all of its child scopes that
+    /// are not yet completed.
+    pub fn pending_count(self) -> ReadSignal<u32> {
+        create_selector(move || self._pending_count())
+    }
+
+    /// Returns a signal with all the tasks in this scope and all of its child scopes that are not
+    /// yet completed, in the order in which they were created.
+    pub fn pending_tasks(self) -> ReadSignal<Vec<SuspenseTaskInfo>> {
+        create_memo(move || {
+            let mut tasks = Vec::new();
+            self.collect_pending_tasks(&mut tasks);
+            tasks.sort_by_key(|task| task.id);
+            tasks
+        })
+    }
+
+    /// Returns a signal with the fraction of tasks in this scope and all of its child scopes that
+    /// have completed, from `0.0` to `1.0`. This is useful for rendering progress bars.
+    ///
+    /// All the tasks that were created since the scope was created are counted. If there are no
+    /// tasks, the progress is `1.0`.
+    pub fn progress(self) -> ReadSignal<f64> {
+        create_selector(move || {
+            let started = self._started_count();
+            if started == 0 {
+                1.0
+            } else {
+                f64::from(started - self._pending_count()) / f64::from(started)
+            }
+        })
+    }
+
+    /// Returns a human readable description of all the pending tasks in this scope and its child
+    /// scopes. This is useful for figuring out why a suspense scope never resolves.
+    ///
+    /// This does not track any signals.
+    pub fn debug_dump(self) -> String {
+        let mut buf = String::new();
+        untrack(|| self.write_debug_dump(&mut buf, 0));
+        buf
+    }
+
+    fn write_debug_dump(self, buf: &mut String, depth: usize) {
+        let indent = "  ".repeat(depth);
+        let _ = write!(
+            buf,
+            "{indent}suspense scope #{} ({} pending",
+            self.id,
+            self._pending_count()
+        );
+        if self.abandoned.get() {
+            buf.push_str(", abandoned");
+        }
+        buf.push_str(")\n");
+        let now = now();
+        self.tasks.with(|tasks| {
+            for task in tasks {
+                let _ = writeln!(
+                    buf,
+                    "{indent}  - {task}, pending for {:?}",
+                    now.saturating_sub(task.started_at)
+                );
+            }
+        });
+        self.children.with(|children| {
+            for child in children {
+                child.write_debug_dump(buf, depth + 1);
+            }
+        });
+    }
+
+    fn register_task(mut self, info: SuspenseTaskInfo) {
+        self.tasks.update(|tasks| tasks.push(info));
+        self.tasks_started += 1;
+    }
+
     /// Returns a future that resolves once the scope is no longer loading.
+    ///
+    /// Unlike [`Self::is_loading`], this also waits for the tasks that were created inside of a
+    /// transition.
     pub async fn until_finished(self) {
         let (tx, rx) = oneshot::channel();
         let mut tx = Some(tx);
         create_effect(move || {
-            if !self._is_loading() {
+            if !self._is_busy() {
                 if let Some(tx) = tx.take() {
                     tx.send(()).unwrap();
                 }
@@ -81,31 +265,132 @@ impl SuspenseScope {
     }
 }
 
+/// Information about a suspense task that is not yet completed. See
+/// [`SuspenseScope::pending_tasks`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SuspenseTaskInfo {
+    id: u64,
+    /// Where the task was created. This can be overridden using
+    /// [`SuspenseTaskGuard::with_location`].
+    pub location: &'static Location<'static>,
+    /// A description of what the task is for, such as the name of the async component or the type
+    /// of the resource that created it.
+    pub label: Option<Cow<'static, str>>,
+    /// When the task was created, according to [`now`].
+    pub started_at: Duration,
+    /// Whether the task was created inside of a transition. Such tasks keep the transition
+    /// pending instead of making the suspense scope show its fallback.
+    pub in_transition: bool,
+}
+
+impl SuspenseTaskInfo {
+    /// How long the task has been pending for.
+    pub fn elapsed(&self) -> Duration {
+        now().saturating_sub(self.started_at)
+    }
+}
+
+impl std::fmt::Display for SuspenseTaskInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(label) = &self.label {
+            write!(f, "{label} at ")?;
+        }
+        write!(f, "{}", self.location)?;
+        if self.in_transition {
+            write!(f, " (in transition)")?;
+        }
+        Ok(())
+    }
+}
+
 /// A guard that keeps a suspense scope suspended until it is dropped.
+///
+/// If the guard is created inside [`start_transition`], the task is also registered with the
+/// transition, which is kept pending instead of the suspense scope showing its fallback. The
+/// suspense scope still waits for the task, e.g. in [`SuspenseScope::until_finished`].
 #[derive(Debug)]
 pub struct SuspenseTaskGuard {
+    id: u64,
     scope: Option<SuspenseScope>,
+    /// The detached suspense scope of the transition the guard was created in, if any.
+    transition: Option<SuspenseScope>,
 }
 
 impl SuspenseTaskGuard {
     /// Creates a new suspense task guard. This will suspend the current suspense scope until this
     /// guard is dropped.
+    #[track_caller]
     pub fn new() -> Self {
-        let scope = try_use_context::<SuspenseScope>();
-        if let Some(mut scope) = scope {
-            scope.tasks_remaining += 1;
-        }
-        Self { scope }
+        Self::with_scope(try_use_context::<SuspenseScope>(), Location::caller())
     }
 
     /// Create a new suspense task guard from a suspense scope.
-    pub fn from_scope(mut scope: SuspenseScope) -> Self {
-        scope.tasks_remaining += 1;
-        Self { scope: Some(scope) }
+    #[track_caller]
+    pub fn from_scope(scope: SuspenseScope) -> Self {
+        Self::with_scope(Some(scope), Location::caller())
+    }
+
+    fn with_scope(scope: Option<SuspenseScope>, location: &'static Location<'static>) -> Self {
+        let id = next_id();
+        let transition = TransitionHandle::current().map(|transition| transition.scope);
+        let info = SuspenseTaskInfo {
+            id,
+            location,
+            label: None,
+            started_at: now(),
+            in_transition: transition.is_some(),
+        };
+        if let Some(scope) = scope {
+            scope.register_task(info.clone());
+        }
+        if let Some(transition) = transition {
+            // The task suspends the scope of the transition as usual, which is what makes the
+            // transition pending.
+            transition.register_task(SuspenseTaskInfo {
+                in_transition: false,
+                ..info
+            });
+        }
+        Self {
+            id,
+            scope,
+            transition,
+        }
+    }
+
+    /// Returns the scopes that the task is registered with.
+    fn scopes(&self) -> impl Iterator<Item = SuspenseScope> {
+        self.scope.into_iter().chain(self.transition)
+    }
+
+    /// Sets the label that is shown for this task in [`SuspenseScope::pending_tasks`].
+    pub fn with_label(self, label: impl Into<Cow<'static, str>>) -> Self {
+        let label = label.into();
+        self.update_info(|info| info.label = Some(label.clone()));
+        self
+    }
+
+    /// Sets the location that is shown for this task in [`SuspenseScope::pending_tasks`].
+    ///
+    /// By default, this is where the guard was created.
+    pub fn with_location(self, location: &'static Location<'static>) -> Self {
+        self.update_info(|info| info.location = location);
+        self
+    }
+
+    fn update_info(&self, f: impl Fn(&mut SuspenseTaskInfo)) {
+        for scope in self.scopes() {
+            scope.tasks.update_silent(|tasks| {
+                if let Some(info) = tasks.iter_mut().find(|info| info.id == self.id) {
+                    f(info);
+                }
+            });
+        }
     }
 }
 
 impl Default for SuspenseTaskGuard {
+    #[track_caller]
     fn default() -> Self {
         Self::new()
     }
@@ -113,8 +398,14 @@ impl Default for SuspenseTaskGuard {
 
 impl Drop for SuspenseTaskGuard {
     fn drop(&mut self) {
-        if let Some(mut scope) = self.scope {
-            scope.tasks_remaining -= 1;
+        // The guard can outlive its scope if it is held by a task that is aborted because the
+        // scope was disposed.
+        for scope in self.scopes() {
+            if scope.tasks.is_alive() {
+                scope
+                    .tasks
+                    .update(|tasks| tasks.retain(|info| info.id != self.id));
+            }
         }
     }
 }
@@ -125,6 +416,7 @@ impl Drop for SuspenseTaskGuard {
 /// there is some async task that should be awaited before showing the UI.
 ///
 /// If this is called from outside a suspense scope, the task will be executed normally.
+#[track_caller]
 pub fn create_suspense_task(f: impl Future<Output = ()> + 'static) {
     let guard = SuspenseTaskGuard::new();
     spawn_local_scoped(async move {
@@ -133,6 +425,20 @@ pub fn create_suspense_task(f: impl Future<Output = ()> + 'static) {
     });
 }
 
+/// Like [`create_suspense_task`] but with a label that is shown in
+/// [`SuspenseScope::pending_tasks`].
+#[track_caller]
+pub fn create_labeled_suspense_task(
+    label: impl Into<Cow<'static, str>>,
+    f: impl Future<Output = ()> + 'static,
+) {
+    let guard = SuspenseTaskGuard::new().with_label(label);
+    spawn_local_scoped(async move {
+        f.await;
+        drop(guard);
+    });
+}
+
 /// Create a new suspense scope that is detached from the rest of the suspense hierarchy.
 ///
 /// This is useful if you want the result of this suspense to be independent of the parent suspense
@@ -202,14 +508,47 @@ pub fn use_is_loading() -> ReadSignal<bool> {
 /// not apply to any suspense scope in particular.
 pub fn use_is_loading_global() -> bool {
     if let Some(global) = try_use_context::<AllTasksRemaining>() {
-        global
-            .all_tasks_remaining
-            .with(|vec| vec.iter().any(|signal| signal.get() > 0))
+        global.all_tasks_remaining.with(|vec| {
+            vec.iter().any(|scope| {
+                // Scopes that have been disposed are no longer loading.
+                scope.abandoned.is_alive() && scope.has_own_tasks() && !scope.is_abandoned()
+            })
+        })
     } else {
         false
     }
 }
 
+/// Returns a signal with the progress of the current suspense scope, from `0.0` to `1.0`. See
+/// [`SuspenseScope::progress`].
+///
+/// If it is called outside of a suspense scope, the signal will always be `1.0`.
+pub fn use_suspense_progress() -> ReadSignal<f64> {
+    try_use_context::<SuspenseScope>().map_or(*create_signal(1.0), |scope| scope.progress())
+}
+
+/// Returns a human readable description of all the suspense tasks that are not yet completed,
+/// grouped by suspense scope. See [`SuspenseScope::debug_dump`].
+///
+/// This is useful for diagnosing suspense that never resolves, e.g. during SSR.
+pub fn debug_dump_pending_suspense() -> String {
+    let Some(global) = try_use_context::<AllTasksRemaining>() else {
+        return String::new();
+    };
+    untrack(|| {
+        global.all_tasks_remaining.with(|vec| {
+            vec.iter()
+                .filter(|scope| {
+                    scope.abandoned.is_alive()
+                        && scope.parent.is_none()
+                        && scope._pending_count() > 0
+                })
+                .map(|scope| scope.debug_dump())
+                .collect()
+        })
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use std::cell::Cell;
@@ -234,6 +573,68 @@ mod tests {
         });
     }
 
+    #[test]
+    fn abandoned_scopes_are_not_loading() {
+        with_test_executor(|_| {
+            let (_, outer) = create_suspense_scope(|| {
+                let (_, inner) = create_suspense_scope(|| {
+                    create_suspense_task(futures::future::pending());
+                });
+                assert!(!inner.is_abandoned());
+            });
+            let is_loading_global = create_selector(use_is_loading_global);
+            assert!(is_loading_global.get());
+
+            outer.abandon();
+            assert!(outer.is_abandoned());
+            assert!(!is_loading_global.get());
+        });
+    }
+
+    #[test]
+    fn pending_tasks_and_progress() {
+        with_test_executor(|executor| {
+            let (inner, outer) = create_suspense_scope(|| {
+                create_labeled_suspense_task("first", sleep(Duration::from_secs(1)));
+                let ((), inner) = create_suspense_scope(|| {
+                    create_suspense_task(sleep(Duration::from_secs(2)));
+                });
+                inner
+            });
+            let pending_count = outer.pending_count();
+            let pending_tasks = outer.pending_tasks();
+            let progress = outer.progress();
+            assert_eq!(pending_count.get(), 2);
+            assert_eq!(progress.get(), 0.0);
+            let tasks = pending_tasks.get_clone();
+            assert_eq!(tasks[0].label.as_deref(), Some("first"));
+            assert_eq!(tasks[1].label, None);
+            assert_eq!(tasks[1].location.file(), file!());
+
+            executor.advance_time(Duration::from_secs(1));
+            assert_eq!(pending_count.get(), 1);
+            assert_eq!(progress.get(), 0.5);
+            // The elapsed time is measured using the clock of the current executor.
+            let elapsed = pending_tasks.with(|tasks| tasks[0].elapsed());
+            assert_eq!(elapsed, Duration::from_secs(1));
+
+            let dump = outer.debug_dump();
+            let expected = format!(
+                "suspense scope #{} (1 pending)\n  suspense scope #{} (1 pending)\n    - {}, pending for 1s\n",
+                outer.id,
+                inner.id,
+                tasks[1],
+            );
+            assert_eq!(dump, expected);
+            assert_eq!(debug_dump_pending_suspense(), dump);
+
+            executor.advance_time(Duration::from_secs(1));
+            assert_eq!(pending_count.get(), 0);
+            assert_eq!(progress.get(), 1.0);
+            assert_eq!(debug_dump_pending_suspense(), "");
+        });
+    }
+
     #[tokio::test]
     async fn suspense_await_suspense() {
         let (tx, rx) = oneshot::channel();
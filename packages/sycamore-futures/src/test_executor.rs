@@ -0,0 +1,452 @@
+//! A deterministic executor and fake clock for testing async code.
+//!
+//! [`TestExecutor`] never runs anything in the background. Tasks are only polled when
+//! [`TestExecutor::run_until_stalled`] or [`TestExecutor::advance_time`] is called, and time only
+//! moves forward when [`TestExecutor::advance_time`] is called. This makes it possible to inspect
+//! the state of the app in between each step.
+//!
+//! This module is only available with the `test-utils` feature.
+
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::time::Duration;
+
+use futures::future::LocalBoxFuture;
+use futures::task::ArcWake;
+use futures::{Future, FutureExt};
+use sycamore_reactive::{create_effect, create_root, create_signal, untrack, Signal};
+
+use crate::{spawn_local, with_scoped_executor, Executor};
+
+/// A single-threaded [`Executor`] that runs tasks step by step and uses a fake clock.
+///
+/// # Example
+/// ```
+/// # use std::time::Duration;
+/// # use sycamore_futures::*;
+/// let executor = TestExecutor::new();
+/// executor.enter(|| {
+///     spawn_local(async {
+///         sleep(Duration::from_secs(1)).await;
+///     });
+/// });
+///
+/// executor.run_until_stalled();
+/// assert_eq!(executor.pending_tasks(), 1);
+///
+/// executor.advance_time(Duration::from_secs(1));
+/// assert_eq!(executor.pending_tasks(), 0);
+/// ```
+#[derive(Clone, Default)]
+pub struct TestExecutor {
+    inner: Rc<Inner>,
+}
+
+#[derive(Default)]
+struct Inner {
+    tasks: RefCell<HashMap<usize, LocalBoxFuture<'static, ()>>>,
+    next_id: Cell<usize>,
+    /// The ids of the tasks that have been woken up. This is shared with the [`Waker`]s, which is
+    /// why it needs to be thread-safe.
+    ready: Arc<Mutex<VecDeque<usize>>>,
+    now: Cell<Duration>,
+    timers: RefCell<Vec<(Duration, Rc<Timer>)>>,
+}
+
+/// Shared state between a [`Sleep`] future and the executor.
+#[derive(Default)]
+struct Timer {
+    fired: Cell<bool>,
+    waker: RefCell<Option<Waker>>,
+}
+
+impl Timer {
+    fn fire(&self) {
+        self.fired.set(true);
+        if let Some(waker) = self.waker.borrow_mut().take() {
+            waker.wake();
+        }
+    }
+}
+
+/// Wakes up a task by pushing its id onto the ready queue.
+struct TaskWaker {
+    id: usize,
+    ready: Arc<Mutex<VecDeque<usize>>>,
+}
+
+impl ArcWake for TaskWaker {
+    fn wake_by_ref(arc_self: &Arc<Self>) {
+        arc_self.ready.lock().unwrap().push_back(arc_self.id);
+    }
+}
+
+/// The future returned by [`TestExecutor::sleep`].
+struct Sleep {
+    timer: Rc<Timer>,
+}
+
+impl Future for Sleep {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if self.timer.fired.get() {
+            Poll::Ready(())
+        } else {
+            *self.timer.waker.borrow_mut() = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+impl TestExecutor {
+    /// Creates a new `TestExecutor` with no tasks and with the clock set to zero.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Runs `f` with this executor as the current executor. All futures that are spawned inside
+    /// `f` using [`spawn_local`] or [`spawn_local_scoped`](crate::spawn_local_scoped) will be
+    /// spawned onto this executor.
+    ///
+    /// Note that the spawned futures are not polled until [`TestExecutor::run_until_stalled`] is
+    /// called.
+    pub fn enter<U>(&self, f: impl FnOnce() -> U) -> U {
+        with_scoped_executor(Rc::new(self.clone()), f)
+    }
+
+    /// Polls all tasks that are ready until no more progress can be made without advancing time
+    /// or some external event.
+    pub fn run_until_stalled(&self) {
+        loop {
+            let Some(id) = self.inner.ready.lock().unwrap().pop_front() else {
+                break;
+            };
+            // The task might already have completed if it was woken up more than once.
+            let Some(mut task) = self.inner.tasks.borrow_mut().remove(&id) else {
+                continue;
+            };
+
+            let waker = futures::task::waker(Arc::new(TaskWaker {
+                id,
+                ready: Arc::clone(&self.inner.ready),
+            }));
+            let mut cx = Context::from_waker(&waker);
+            // Make sure that any futures spawned by the task are spawned onto this executor.
+            // The task is removed from the map while it is being polled so that it can spawn
+            // new tasks.
+            if self.enter(|| task.poll_unpin(&mut cx)).is_pending() {
+                self.inner.tasks.borrow_mut().insert(id, task);
+            }
+        }
+    }
+
+    /// Moves the fake clock forward by `duration`, firing all timers that expire along the way.
+    ///
+    /// Timers are fired in order of their deadline and all ready tasks are run until stalled in
+    /// between, so a task that sleeps repeatedly will observe every tick.
+    pub fn advance_time(&self, duration: Duration) {
+        let target = self.inner.now.get() + duration;
+        loop {
+            self.run_until_stalled();
+
+            let next = self
+                .inner
+                .timers
+                .borrow()
+                .iter()
+                .map(|(deadline, _)| *deadline)
+                .filter(|deadline| *deadline <= target)
+                .min();
+            let Some(next) = next else {
+                break;
+            };
+            self.inner.now.set(next);
+
+            let expired = {
+                let mut timers = self.inner.timers.borrow_mut();
+                let (expired, pending) = timers
+                    .drain(..)
+                    .partition(|(deadline, _)| *deadline <= next);
+                *timers = pending;
+                expired
+            };
+            for (_, timer) in expired {
+                timer.fire();
+            }
+        }
+        self.inner.now.set(target);
+        self.run_until_stalled();
+    }
+
+    /// Runs `fut` on this executor until stalled, returning its output if it has completed.
+    pub fn run<T: 'static>(&self, fut: impl Future<Output = T> + 'static) -> Option<T> {
+        let output = Rc::new(RefCell::new(None));
+        self.enter(|| {
+            spawn_local({
+                let output = Rc::clone(&output);
+                async move {
+                    *output.borrow_mut() = Some(fut.await);
+                }
+            })
+        });
+        self.run_until_stalled();
+        output.take()
+    }
+
+    /// Returns the current time of the fake clock. This starts at zero.
+    pub fn now(&self) -> Duration {
+        self.inner.now.get()
+    }
+
+    /// Returns the number of tasks that have not yet completed.
+    pub fn pending_tasks(&self) -> usize {
+        self.inner.tasks.borrow().len()
+    }
+}
+
+impl Executor for TestExecutor {
+    fn spawn_local(&self, fut: LocalBoxFuture<'static, ()>) {
+        let id = self.inner.next_id.get();
+        self.inner.next_id.set(id + 1);
+        self.inner.tasks.borrow_mut().insert(id, fut);
+        self.inner.ready.lock().unwrap().push_back(id);
+    }
+
+    fn sleep(&self, duration: Duration) -> LocalBoxFuture<'static, ()> {
+        let timer = Rc::new(Timer::default());
+        if duration.is_zero() {
+            timer.fired.set(true);
+        } else {
+            let deadline = self.inner.now.get() + duration;
+            self.inner
+                .timers
+                .borrow_mut()
+                .push((deadline, Rc::clone(&timer)));
+        }
+        Sleep { timer }.boxed_local()
+    }
+
+    fn now(&self) -> Duration {
+        self.inner.now.get()
+    }
+}
+
+impl fmt::Debug for TestExecutor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TestExecutor")
+            .field("now", &self.now())
+            .field("pending_tasks", &self.pending_tasks())
+            .finish()
+    }
+}
+
+/// Creates a new reactive root and runs `f` inside of it with a new [`TestExecutor`] as the current
+/// executor. The root is disposed once `f` returns.
+///
+/// All futures that are spawned inside `f`, including the ones spawned later on by effects, go
+/// onto the executor, which is passed to `f` so that it can be stepped through.
+///
+/// # Example
+/// ```
+/// # use std::time::Duration;
+/// # use sycamore_futures::*;
+/// # use sycamore_reactive::*;
+/// with_test_executor(|executor| {
+///     let done = create_signal(false);
+///     spawn_local_scoped(async move {
+///         sleep(Duration::from_secs(1)).await;
+///         done.set(true);
+///     });
+///
+///     executor.run_until_stalled();
+///     assert!(!done.get());
+///     executor.advance_time(Duration::from_secs(1));
+///     assert!(done.get());
+/// });
+/// ```
+pub fn with_test_executor(f: impl FnOnce(&TestExecutor)) {
+    let executor = TestExecutor::new();
+    let root = create_root(|| executor.enter(|| f(&executor)));
+    root.dispose();
+}
+
+/// Records every value that a reactive expression takes on. Created using
+/// [`create_signal_history`].
+pub struct SignalHistory<T: 'static> {
+    values: Signal<Vec<T>>,
+}
+
+impl<T> Clone for SignalHistory<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<T> Copy for SignalHistory<T> {}
+
+impl<T: Clone> SignalHistory<T> {
+    /// Returns all the values that have been recorded so far.
+    pub fn values(self) -> Vec<T> {
+        self.values.get_clone_untracked()
+    }
+
+    /// Returns all the values that have been recorded so far and clears the history.
+    pub fn take(self) -> Vec<T> {
+        self.values.update_silent(std::mem::take)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for SignalHistory<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.values
+            .with_untracked(|values| f.debug_list().entries(values).finish())
+    }
+}
+
+/// Records the value of `f` every time it changes. This is useful for asserting the transitions of
+/// reactive state, such as [`use_is_loading`](crate::use_is_loading), in between steps of a
+/// [`TestExecutor`].
+///
+/// Consecutive duplicate values are only recorded once. This must be called inside a reactive
+/// scope.
+///
+/// # Example
+/// ```
+/// # use sycamore_futures::*;
+/// # use sycamore_reactive::*;
+/// # let _ = create_root(|| {
+/// let signal = create_signal(0);
+/// let history = create_signal_history(move || signal.get());
+/// signal.set(1);
+/// signal.set(1);
+/// signal.set(2);
+/// assert_eq!(history.take(), [0, 1, 2]);
+/// # });
+/// ```
+pub fn create_signal_history<T: Clone + PartialEq + 'static>(
+    f: impl Fn() -> T + 'static,
+) -> SignalHistory<T> {
+    let values = create_signal(Vec::<T>::new());
+    create_effect(move || {
+        let value = f();
+        untrack(|| {
+            if values.with(|values| values.last() != Some(&value)) {
+                values.update_silent(|values| values.push(value));
+            }
+        });
+    });
+    SignalHistory { values }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{create_suspense_scope, create_suspense_task, now, sleep, use_is_loading};
+
+    #[test]
+    fn tasks_only_run_when_stepped() {
+        let executor = TestExecutor::new();
+        let counter = Rc::new(Cell::new(0));
+        executor.enter(|| {
+            let counter = Rc::clone(&counter);
+            spawn_local(async move {
+                counter.set(counter.get() + 1);
+                // Nested spawns should also go onto the test executor.
+                spawn_local(async move { counter.set(counter.get() + 1) });
+            });
+        });
+        assert_eq!(counter.get(), 0);
+        assert_eq!(executor.pending_tasks(), 1);
+
+        executor.run_until_stalled();
+        assert_eq!(counter.get(), 2);
+        assert_eq!(executor.pending_tasks(), 0);
+    }
+
+    #[test]
+    fn advance_time_fires_timers_in_order() {
+        let executor = TestExecutor::new();
+        let log = Rc::new(RefCell::new(Vec::new()));
+        executor.enter(|| {
+            for ms in [300, 100, 200] {
+                let log = Rc::clone(&log);
+                spawn_local(async move {
+                    sleep(Duration::from_millis(ms)).await;
+                    log.borrow_mut().push((ms, now()));
+                });
+            }
+        });
+
+        executor.advance_time(Duration::from_millis(150));
+        assert_eq!(*log.borrow(), [(100, Duration::from_millis(100))]);
+        assert_eq!(executor.now(), Duration::from_millis(150));
+
+        executor.advance_time(Duration::from_millis(150));
+        assert_eq!(
+            *log.borrow(),
+            [
+                (100, Duration::from_millis(100)),
+                (200, Duration::from_millis(200)),
+                (300, Duration::from_millis(300)),
+            ]
+        );
+        assert_eq!(executor.pending_tasks(), 0);
+    }
+
+    #[test]
+    fn repeated_sleeps_observe_every_tick() {
+        let executor = TestExecutor::new();
+        let ticks = Rc::new(Cell::new(0));
+        executor.enter(|| {
+            let ticks = Rc::clone(&ticks);
+            spawn_local(async move {
+                loop {
+                    sleep(Duration::from_secs(1)).await;
+                    ticks.set(ticks.get() + 1);
+                }
+            });
+        });
+        executor.advance_time(Duration::from_millis(3500));
+        assert_eq!(ticks.get(), 3);
+    }
+
+    #[test]
+    fn run_returns_output() {
+        let executor = TestExecutor::new();
+        assert_eq!(executor.run(async { 123 }), Some(123));
+        assert_eq!(
+            executor.run(async {
+                sleep(Duration::from_secs(1)).await;
+            }),
+            None
+        );
+    }
+
+    #[test]
+    fn is_loading_transitions() {
+        with_test_executor(|executor| {
+            let (_, scope) = create_suspense_scope(|| {
+                create_suspense_task(sleep(Duration::from_secs(1)));
+                create_suspense_task(sleep(Duration::from_secs(2)));
+            });
+            let is_loading = scope.is_loading();
+            let history = create_signal_history(move || is_loading.get());
+            assert_eq!(history.take(), [true]);
+
+            executor.advance_time(Duration::from_secs(1));
+            assert_eq!(history.take(), []);
+
+            executor.advance_time(Duration::from_secs(1));
+            assert_eq!(history.take(), [false]);
+
+            // `use_is_loading` outside of a suspense scope is always false.
+            let history = create_signal_history(move || use_is_loading().get());
+            assert_eq!(history.values(), [false]);
+        });
+    }
+}
@@ -0,0 +1,203 @@
+//! Transitions for deferring state updates behind suspense.
+//!
+//! Normally, when a state update causes a new suspense task to be created, the nearest suspense
+//! boundary shows its fallback until the task is completed. Inside [`start_transition`], the task
+//! is also tracked by the transition, which holds the boundary: the old UI is kept on screen
+//! instead of the fallback and the pending state is exposed through
+//! [`TransitionHandle::is_pending`]. The boundary still waits for the task, so it is, for example,
+//! still awaited when rendering on the server.
+
+use std::cell::Cell;
+
+use sycamore_reactive::*;
+
+use crate::*;
+
+thread_local! {
+    /// The transition that is currently being started, if any.
+    static CURRENT_TRANSITION: Cell<Option<TransitionHandle>> = const { Cell::new(None) };
+}
+
+/// A handle to a transition. Created using [`use_transition`].
+#[derive(Debug, Clone, Copy)]
+pub struct TransitionHandle {
+    /// A detached suspense scope that all the suspense tasks created inside the transition are
+    /// registered with, in addition to their own suspense scope.
+    pub(crate) scope: SuspenseScope,
+    is_pending: ReadSignal<bool>,
+}
+
+impl TransitionHandle {
+    fn new() -> Self {
+        let ((), scope) = create_detached_suspense_scope(|| {});
+        Self {
+            scope,
+            is_pending: scope.is_loading(),
+        }
+    }
+
+    /// Returns the transition that is currently being started, if any.
+    pub(crate) fn current() -> Option<Self> {
+        CURRENT_TRANSITION.get()
+    }
+
+    /// Runs `f` as part of this transition.
+    ///
+    /// All suspense tasks that are created synchronously while running `f`, for example by a
+    /// resource that is refetched or an async component that is mounted because a signal was
+    /// updated, keep the transition pending instead of making their suspense boundary show its
+    /// fallback. This means that the previous content stays on screen instead of being replaced
+    /// by the fallback. The boundary still waits for the tasks, e.g. in
+    /// [`SuspenseScope::until_finished`].
+    pub fn start(self, f: impl FnOnce()) {
+        /// Restores the previous transition even if `f` panics.
+        struct Restore(Option<TransitionHandle>);
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                CURRENT_TRANSITION.set(self.0);
+            }
+        }
+
+        let _restore = Restore(CURRENT_TRANSITION.replace(Some(self)));
+        f();
+    }
+
+    /// Returns a signal that is `true` while there are suspense tasks from this transition that
+    /// are not yet completed.
+    pub fn is_pending(self) -> ReadSignal<bool> {
+        self.is_pending
+    }
+
+    /// Returns a signal with all the suspense tasks from this transition that are not yet
+    /// completed. See [`SuspenseScope::pending_tasks`].
+    pub fn pending_tasks(self) -> ReadSignal<Vec<SuspenseTaskInfo>> {
+        self.scope.pending_tasks()
+    }
+}
+
+/// Creates a new transition in the current scope.
+///
+/// Use [`TransitionHandle::start`] to mark state updates as part of the transition and
+/// [`TransitionHandle::is_pending`] to show a pending indicator.
+///
+/// # Example
+/// ```
+/// # use sycamore_futures::*;
+/// # use sycamore_reactive::*;
+/// # let _ = create_root(|| {
+/// let tab = create_signal(0);
+/// let transition = use_transition();
+/// let switch_tab = move |new_tab| transition.start(|| tab.set(new_tab));
+/// let is_pending = transition.is_pending();
+/// # });
+/// ```
+pub fn use_transition() -> TransitionHandle {
+    TransitionHandle::new()
+}
+
+/// Runs `f` as part of a global transition.
+///
+/// This is like [`TransitionHandle::start`] except that it does not require a handle. The pending
+/// state of the global transition can be accessed with [`use_global_transition`].
+pub fn start_transition(f: impl FnOnce()) {
+    use_global_transition().start(f);
+}
+
+/// Returns the transition that is used by [`start_transition`].
+pub fn use_global_transition() -> TransitionHandle {
+    use_global_scope().run_in(|| use_context_or_else(TransitionHandle::new))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn transition_keeps_suspense_resolved() {
+        with_test_executor(|executor| {
+            let trigger = create_signal(0);
+            let transition = use_transition();
+            let (_, scope) = create_suspense_scope(|| {
+                create_effect(move || {
+                    trigger.track();
+                    create_suspense_task(sleep(Duration::from_secs(1)));
+                });
+            });
+            let is_loading = scope.is_loading();
+            executor.advance_time(Duration::from_secs(1));
+            assert!(!is_loading.get());
+
+            // A transition keeps the suspense scope resolved but is pending.
+            transition.start(|| trigger.set(1));
+            assert!(!is_loading.get());
+            assert!(transition.is_pending().get());
+            // The suspense scope still waits for the task.
+            assert_eq!(scope.pending_count().get(), 1);
+            assert!(scope.pending_tasks().get_clone()[0].in_transition);
+            assert_eq!(transition.pending_tasks().get_clone().len(), 1);
+            let finished = create_signal(false);
+            spawn_local_scoped(async move {
+                scope.until_finished().await;
+                finished.set(true);
+            });
+            executor.run_until_stalled();
+            assert!(!finished.get());
+
+            executor.advance_time(Duration::from_secs(1));
+            assert!(!transition.is_pending().get());
+            assert!(finished.get());
+
+            // An urgent update suspends the suspense scope as usual.
+            trigger.set(2);
+            assert!(is_loading.get());
+            assert!(!transition.is_pending().get());
+
+            executor.advance_time(Duration::from_secs(1));
+            assert!(!is_loading.get());
+        });
+    }
+
+    #[test]
+    fn tasks_mounted_in_transition_are_registered_with_scope() {
+        with_test_executor(|executor| {
+            let tab = create_signal(0);
+            let transition = use_transition();
+            let (_, scope) = create_suspense_scope(|| {
+                create_effect(move || {
+                    // Simulates mounting a new async component when the tab changes.
+                    if tab.get() == 1 {
+                        let _ = create_suspense_scope(|| {
+                            create_suspense_task(sleep(Duration::from_secs(1)));
+                        });
+                    }
+                });
+            });
+            let is_loading = scope.is_loading();
+
+            transition.start(|| tab.set(1));
+            assert!(!is_loading.get());
+            assert!(transition.is_pending().get());
+            assert_eq!(scope.pending_count().get(), 1);
+            assert!(use_is_loading_global());
+
+            executor.advance_time(Duration::from_secs(1));
+            assert!(!transition.is_pending().get());
+            assert_eq!(scope.pending_count().get(), 0);
+        });
+    }
+
+    #[test]
+    fn start_transition_uses_global_transition() {
+        with_test_executor(|executor| {
+            let is_pending = use_global_transition().is_pending();
+            start_transition(|| create_suspense_task(sleep(Duration::from_secs(1))));
+            assert!(is_pending.get());
+            assert!(TransitionHandle::current().is_none());
+
+            executor.advance_time(Duration::from_secs(1));
+            assert!(!is_pending.get());
+        });
+    }
+}
@@ -193,6 +193,7 @@ impl ToTokens for ComponentFn {
                 inputs: sync_input,
                 ..sig.clone()
             };
+            let component_ident = &sig.ident;
             let inner_ident = format_ident!("{}_inner", sig.ident);
             let inner_sig = Signature {
                 ident: inner_ident.clone(),
@@ -208,7 +209,10 @@ impl ToTokens for ComponentFn {
                     #[allow(non_snake_case)]
                     #inner_sig #block
 
-                    ::sycamore::rt::WrapAsync(move || #inner_ident(#(#args),*))
+                    ::sycamore::rt::wrap_async_named(
+                        ::std::concat!(::std::module_path!(), "::", ::std::stringify!(#component_ident)),
+                        move || #inner_ident(#(#args),*),
+                    )
                 }
             });
         } else {
@@ -270,6 +274,59 @@ pub fn component_impl(args: ComponentArgs, item: TokenStream) -> Result<TokenStr
     }
 }
 
+pub fn island_impl(args: ComponentArgs, item: TokenStream) -> Result<TokenStream> {
+    let mut item_fn = syn::parse::<ItemFn>(item.into())?;
+    if let Some(asyncness) = item_fn.sig.asyncness {
+        return Err(Error::new(asyncness.span(), "islands can't be async"));
+    }
+    let inline_props = if args.inline_props.is_some() {
+        Some(inline_props_impl(&mut item_fn, args.attrs)?)
+    } else {
+        None
+    };
+    // Parse the function as a component to get the same validation.
+    let ComponentFn { f } = syn::parse::<ComponentFn>(item_fn.to_token_stream().into())?;
+    let ItemFn {
+        attrs,
+        vis,
+        sig,
+        block,
+    } = f;
+
+    // The island is rendered by a nested function, like async components. The outer component
+    // serializes the props and wraps the view.
+    let name = sig.ident.to_string();
+    let inner_ident = format_ident!("{}_inner", sig.ident);
+    let inner_sig = Signature {
+        ident: inner_ident.clone(),
+        ..sig.clone()
+    };
+    let AsyncCompInputs {
+        sync_input,
+        async_args: args,
+    } = async_comp_inputs_from_sig_inputs(&sig.inputs);
+    let outer_sig = Signature {
+        inputs: sync_input,
+        ..sig
+    };
+    let create = match args.first() {
+        Some(props) => quote! { ::sycamore::rt::create_island(#name, #props, #inner_ident) },
+        None => quote! { ::sycamore::rt::create_island(#name, (), |()| #inner_ident()) },
+    };
+
+    Ok(quote! {
+        #inline_props
+        #(#attrs)*
+        #[::sycamore::component]
+        #vis #outer_sig {
+            #[allow(non_snake_case)]
+            #inner_sig #block
+
+            #create
+        }
+    })
+}
+
 /// Codegens the new props struct and modifies the component body to accept this new struct as
 /// props.
 fn inline_props_impl(item: &mut ItemFn, attrs: Punctuated<Meta, Token![,]>) -> Result<TokenStream> {
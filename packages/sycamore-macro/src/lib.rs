@@ -45,6 +45,30 @@ pub fn component(args: TokenStream, item: TokenStream) -> TokenStream {
         .into()
 }
 
+/// A macro for creating islands from functions. Requires the `islands` feature.
+///
+/// An island is a component that is hydrated on its own on the client while the rest of the page
+/// stays static. The props are serialized on the server using `serde`, so they must implement
+/// `Serialize` and `Deserialize`. This accepts the same arguments as [`macro@component`] but the
+/// function cannot be `async`.
+///
+/// On the client, islands are hydrated using `sycamore::web::Islands`.
+#[proc_macro_attribute]
+pub fn island(args: TokenStream, item: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(args as component::ComponentArgs);
+
+    component::island_impl(args, item.clone().into())
+        .unwrap_or_else(|err| {
+            let error_tokens = err.into_compile_error();
+            let body_input = proc_macro2::TokenStream::from(item);
+            quote! {
+                #body_input
+                #error_tokens
+            }
+        })
+        .into()
+}
+
 /// The derive macro for `Props`. The macro creates a builder-like API used in the [`view!`] macro.
 #[proc_macro_derive(Props, attributes(prop))]
 pub fn derive_props(input: TokenStream) -> TokenStream {
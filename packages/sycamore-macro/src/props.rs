@@ -265,9 +265,10 @@ mod struct_info {
                         fn set_event_handler(
                             &mut self,
                             name: &'static ::std::primitive::str,
+                            options: ::sycamore::rt::events::EventOptions,
                             handler: impl ::std::ops::FnMut(::sycamore::rt::Event) + 'static,
                         ) {
-                            self.attributes.set_event_handler(name, handler);
+                            self.attributes.set_event_handler(name, options, handler);
                         }
                     }
                 }
@@ -851,7 +852,7 @@ mod field_info {
             ordinal: usize,
             field: &syn::Field,
             field_defaults: FieldBuilderAttr,
-        ) -> Result<FieldInfo, Error> {
+        ) -> Result<FieldInfo<'_>, Error> {
             if let Some(ref name) = field.ident {
                 let mut builder_attr = field_defaults.with(&field.attrs)?;
 
@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+use sycamore::prelude::{island, Props, View};
+
+#[derive(Props, Serialize, Deserialize)]
+struct IslandProps {
+    value: i32,
+}
+
+#[island]
+fn IslandNoProps() -> View {
+    todo!();
+}
+
+#[island]
+fn IslandWithProps(props: IslandProps) -> View {
+    let _ = props;
+    todo!();
+}
+
+#[island(inline_props, derive(Serialize, Deserialize))]
+fn IslandInlineProps(value: i32) -> View {
+    let _ = value;
+    todo!();
+}
+
+fn main() {}
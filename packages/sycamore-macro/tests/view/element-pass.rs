@@ -13,6 +13,9 @@ fn compile_pass() {
         let _: View = view! { p("attr-42"="my-value") };
 
         let _: View = view! { button(class="my-btn", on:click=|_| {}) };
+        let _: View = view! { form(on:submit|prevent=|_| {}) };
+        let _: View = view! { div(on:click|self|stop|once=|_| {}) };
+        let _: View = view! { div(on:scroll|capture|passive=|_| {}) };
         let _: View = view! { button(class="my-btn", aria-hidden="true") };
 
         let _: View = view! { p(dangerously_set_inner_html="<span>Test</span>") };
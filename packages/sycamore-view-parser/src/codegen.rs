@@ -5,7 +5,7 @@
 
 use proc_macro2::TokenStream;
 use quote::quote;
-use syn::{Expr, Pat};
+use syn::{Expr, Ident, Pat};
 
 use crate::ir::{DynNode, Node, Prop, PropType, Root, TagIdent, TagNode, TextNode};
 
@@ -109,7 +109,14 @@ impl Codegen {
             PropType::PlainQuoted { ident } => {
                 quote! { .attr(#ident, #dyn_value) }
             }
-            PropType::Directive { dir, ident } => match dir.to_string().as_str() {
+            // Modifiers are validated while parsing and are only allowed on `on`.
+            PropType::Directive {
+                ident, modifiers, ..
+            } if !modifiers.is_empty() => {
+                let options = event_options(modifiers);
+                quote! { .on_with_options(::sycamore::rt::events::#ident, #options, #value) }
+            }
+            PropType::Directive { dir, ident, .. } => match dir.to_string().as_str() {
                 "on" => quote! { .on(::sycamore::rt::events::#ident, #value) },
                 "prop" => {
                     let ident = ident.to_string();
@@ -166,9 +173,15 @@ impl Codegen {
                 )
             }
         };
+        // Used for the component path in diagnostics.
+        let name = ident
+            .segments
+            .last()
+            .map(|segment| segment.ident.to_string())
+            .unwrap_or_default();
         quote! {{
             let __component = &#ident; // We do this to make sure the compiler can infer the value for `<G>`.
-            ::sycamore::rt::component_scope(move || ::sycamore::rt::Component::create(
+            ::sycamore::rt::named_component_scope(#name, move || ::sycamore::rt::Component::create(
                 __component,
                 ::sycamore::rt::element_like_component_builder(__component)
                     #(.#plain_names(#plain_values))*
@@ -180,6 +193,23 @@ impl Codegen {
     }
 }
 
+/// Generates the `EventOptions` for the modifiers of an `on:` directive.
+fn event_options(modifiers: &[Ident]) -> TokenStream {
+    let methods = modifiers.iter().map(|modifier| {
+        let method = match modifier.to_string().as_str() {
+            "prevent" => "prevent_default",
+            "stop" => "stop_propagation",
+            "once" => "once",
+            "capture" => "capture",
+            "passive" => "passive",
+            "self" => "self_only",
+            _ => unreachable!("modifiers are validated while parsing"),
+        };
+        Ident::new(method, modifier.span())
+    });
+    quote! { ::sycamore::rt::events::EventOptions::new()#(.#methods())* }
+}
+
 fn is_component(ident: &TagIdent) -> bool {
     match ident {
         TagIdent::Path(path) => {
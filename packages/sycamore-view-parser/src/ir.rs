@@ -55,8 +55,12 @@ pub enum PropType {
     PlainHyphenated { ident: String },
     /// Syntax: `"<quoted-name>"=<expr>`.
     PlainQuoted { ident: String },
-    /// Syntax: `<dir>:<prop>=<expr>`.
-    Directive { dir: Ident, ident: Ident },
+    /// Syntax: `<dir>:<prop>|<modifier>...=<expr>`. Modifiers are optional.
+    Directive {
+        dir: Ident,
+        ident: Ident,
+        modifiers: Vec<Ident>,
+    },
     /// Syntax: `r#ref=<expr>`.
     Ref,
     /// Syntax: `..attributes=<expr>`
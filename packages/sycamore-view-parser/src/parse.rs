@@ -112,7 +112,7 @@ impl Parse for Prop {
     fn parse(input: ParseStream) -> Result<Self> {
         let span = input.span();
         let ty = input.parse()?;
-        if !matches!(ty, PropType::Spread { .. }) {
+        if !matches!(ty, PropType::Spread) {
             let _eqs: Token![=] = input.parse()?;
         }
         let value = input.parse()?;
@@ -148,7 +148,17 @@ impl Parse for PropType {
                 } else if input.peek(Token![:]) {
                     let _colon: Token![:] = input.parse()?;
                     let ident = input.call(Ident::parse_any)?;
-                    Ok(Self::Directive { dir: name, ident })
+                    let mut modifiers = Vec::new();
+                    while input.peek(Token![|]) {
+                        let _pipe: Token![|] = input.parse()?;
+                        modifiers.push(input.call(Ident::parse_any)?);
+                    }
+                    validate_modifiers(&name, &modifiers)?;
+                    Ok(Self::Directive {
+                        dir: name,
+                        ident,
+                        modifiers,
+                    })
                 } else {
                     Ok(Self::Plain { ident: name })
                 }
@@ -162,6 +172,44 @@ impl Parse for PropType {
     }
 }
 
+/// The modifiers that are allowed on the `on` directive.
+const EVENT_MODIFIERS: &[&str] = &["prevent", "stop", "once", "capture", "passive", "self"];
+
+/// Checks that the modifiers of a directive are valid so that codegen does not need to.
+fn validate_modifiers(dir: &Ident, modifiers: &[Ident]) -> Result<()> {
+    let Some(first) = modifiers.first() else {
+        return Ok(());
+    };
+    if dir != "on" {
+        return Err(syn::Error::new(
+            first.span(),
+            format!("modifiers are not supported on the `{dir}` directive"),
+        ));
+    }
+    for modifier in modifiers {
+        if !EVENT_MODIFIERS.iter().any(|m| modifier == m) {
+            return Err(syn::Error::new(
+                modifier.span(),
+                format!(
+                    "unknown event modifier `{modifier}`, expected one of `prevent`, `stop`, \
+                     `once`, `capture`, `passive` or `self`"
+                ),
+            ));
+        }
+    }
+    // A passive listener cannot call `preventDefault()`. The error points at whichever of the two
+    // modifiers comes last.
+    let prevent = modifiers.iter().position(|m| m == "prevent");
+    let passive = modifiers.iter().position(|m| m == "passive");
+    if let (Some(prevent), Some(passive)) = (prevent, passive) {
+        return Err(syn::Error::new(
+            modifiers[prevent.max(passive)].span(),
+            "the `prevent` and `passive` event modifiers cannot be used together",
+        ));
+    }
+    Ok(())
+}
+
 impl Parse for TextNode {
     fn parse(input: ParseStream) -> Result<Self> {
         Ok(Self {
@@ -23,12 +23,177 @@ impl AttributeValue for BoolAttribute {
     }
 }
 
-impl AttributeValue for MaybeDyn<JsValue> {
+impl AttributeValue for MaybeDyn<PropertyValue> {
     fn set_self(self, el: &mut HtmlNode, name: Cow<'static, str>) {
         el.set_property(name, self);
     }
 }
 
+impl AttributeValue for MaybeDyn<JsValue> {
+    fn set_self(self, el: &mut HtmlNode, name: Cow<'static, str>) {
+        el.set_property(name, self.into_property_value());
+    }
+}
+
+/// The value of a JS property.
+///
+/// Unlike [`JsValue`], primitive values can also be read in SSR mode. This allows rendering
+/// well-known properties such as `value` and `checked` as their attribute equivalents.
+///
+/// This is the value that is passed to [`ViewHtmlNode::set_property`]. It can be converted back
+/// into a `JsValue` with [`Into`].
+#[derive(Clone)]
+pub enum PropertyValue {
+    /// A JS boolean. Rendered as a boolean attribute in SSR mode, e.g. for `checked`.
+    Bool(bool),
+    /// A JS number. All Rust number types are converted to this.
+    Number(f64),
+    /// A JS string. Rendered as an attribute in SSR mode, e.g. for `value`.
+    String(Cow<'static, str>),
+    /// Any other JS value. These are not rendered in SSR mode.
+    Js(JsValue),
+}
+impl_into_maybe_dyn!(PropertyValue);
+
+impl From<PropertyValue> for JsValue {
+    fn from(value: PropertyValue) -> Self {
+        match value {
+            PropertyValue::Bool(value) => value.into(),
+            PropertyValue::Number(value) => value.into(),
+            PropertyValue::String(value) => JsValue::from_str(&value),
+            PropertyValue::Js(value) => value,
+        }
+    }
+}
+
+impl From<bool> for PropertyValue {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl From<Cow<'static, str>> for PropertyValue {
+    fn from(value: Cow<'static, str>) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<&'static str> for PropertyValue {
+    fn from(value: &'static str) -> Self {
+        Self::String(value.into())
+    }
+}
+
+impl From<String> for PropertyValue {
+    fn from(value: String) -> Self {
+        Self::String(value.into())
+    }
+}
+
+impl From<JsValue> for PropertyValue {
+    fn from(value: JsValue) -> Self {
+        Self::Js(value)
+    }
+}
+
+macro_rules! impl_from_number_for_property_value {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for PropertyValue {
+                fn from(value: $ty) -> Self {
+                    Self::Number(value as f64)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_number_for_property_value!(
+    f32, f64, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize
+);
+
+/// Types that can be converted into a possibly dynamic [`PropertyValue`]. This is what
+/// [`prop`](GlobalAttributes::prop) accepts.
+///
+/// This is implemented for primitive values, strings, [`JsValue`], signals and closures returning
+/// any of these.
+pub trait IntoPropertyValue {
+    fn into_property_value(self) -> MaybeDyn<PropertyValue>;
+}
+
+macro_rules! impl_into_property_value {
+    ($($ty:ty),*) => {
+        $(
+            impl IntoPropertyValue for $ty {
+                fn into_property_value(self) -> MaybeDyn<PropertyValue> {
+                    MaybeDyn::Static(self.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_into_property_value!(
+    PropertyValue,
+    JsValue,
+    bool,
+    Cow<'static, str>,
+    &'static str,
+    String,
+    f32,
+    f64,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize
+);
+
+impl IntoPropertyValue for MaybeDyn<PropertyValue> {
+    fn into_property_value(self) -> MaybeDyn<PropertyValue> {
+        self
+    }
+}
+
+impl IntoPropertyValue for MaybeDyn<JsValue> {
+    fn into_property_value(self) -> MaybeDyn<PropertyValue> {
+        match self {
+            MaybeDyn::Static(value) => MaybeDyn::Static(value.into()),
+            MaybeDyn::Signal(signal) => signal.into_property_value(),
+            MaybeDyn::Derived(f) => MaybeDyn::Derived(Rc::new(move || f().into_property_value())),
+        }
+    }
+}
+
+impl<T: IntoPropertyValue + Clone + 'static> IntoPropertyValue for ReadSignal<T> {
+    fn into_property_value(self) -> MaybeDyn<PropertyValue> {
+        MaybeDyn::Derived(Rc::new(move || self.get_clone().into_property_value()))
+    }
+}
+
+impl<T: IntoPropertyValue + Clone + 'static> IntoPropertyValue for Signal<T> {
+    fn into_property_value(self) -> MaybeDyn<PropertyValue> {
+        (*self).into_property_value()
+    }
+}
+
+impl<F, U> IntoPropertyValue for F
+where
+    F: Fn() -> U + 'static,
+    U: IntoPropertyValue,
+{
+    fn into_property_value(self) -> MaybeDyn<PropertyValue> {
+        MaybeDyn::Derived(Rc::new(move || self().into_property_value()))
+    }
+}
+
 /// Trait used to implement `AttributeValue` for `Box<dyn AttributeValue>`.
 #[doc(hidden)]
 pub trait AttributeValueBoxed: 'static {
@@ -56,6 +221,7 @@ pub trait SetAttribute {
     fn set_event_handler(
         &mut self,
         name: &'static str,
+        options: events::EventOptions,
         value: impl FnMut(web_sys::Event) + 'static,
     );
 }
@@ -71,9 +237,11 @@ where
     fn set_event_handler(
         &mut self,
         name: &'static str,
+        options: events::EventOptions,
         value: impl FnMut(web_sys::Event) + 'static,
     ) {
-        self.as_html_node().set_event_handler(name.into(), value);
+        self.as_html_node()
+            .set_event_handler(name.into(), options, value);
     }
 }
 
@@ -82,7 +250,11 @@ where
 pub struct Attributes {
     values: Vec<(Cow<'static, str>, Box<dyn AttributeValue>)>,
     #[allow(clippy::type_complexity)]
-    event_handlers: Vec<(Cow<'static, str>, Box<dyn FnMut(web_sys::Event)>)>,
+    event_handlers: Vec<(
+        Cow<'static, str>,
+        events::EventOptions,
+        Box<dyn FnMut(web_sys::Event)>,
+    )>,
 }
 
 impl SetAttribute for Attributes {
@@ -93,9 +265,11 @@ impl SetAttribute for Attributes {
     fn set_event_handler(
         &mut self,
         name: &'static str,
+        options: events::EventOptions,
         value: impl FnMut(web_sys::Event) + 'static,
     ) {
-        self.event_handlers.push((name.into(), Box::new(value)));
+        self.event_handlers
+            .push((name.into(), options, Box::new(value)));
     }
 }
 
@@ -109,8 +283,8 @@ impl Attributes {
         for (name, value) in self.values {
             value.set_self(el, name);
         }
-        for (name, handler) in self.event_handlers {
-            el.set_event_handler(name, handler);
+        for (name, options, handler) in self.event_handlers {
+            el.set_event_handler(name, options, handler);
         }
     }
 }
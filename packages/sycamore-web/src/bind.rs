@@ -8,7 +8,7 @@ pub trait BindDescriptor {
     /// The event which we listen to to update the value.
     type Event: EventDescriptor;
     /// The value of the signal that drives the attribute/property.
-    type ValueTy: Into<JsValue> + Clone;
+    type ValueTy: Into<PropertyValue> + Clone;
     /// The name of the property to which we are binding.
     const TARGET_PROPERTY: &'static str;
     /// Function for converting from JS to Rust type.
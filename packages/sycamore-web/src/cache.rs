@@ -0,0 +1,363 @@
+//! Caching the rendered HTML of static parts of the page in SSR mode.
+
+#[cfg_ssr]
+use std::collections::HashMap;
+#[cfg_not_ssr]
+#[cfg(feature = "hydrate")]
+use std::num::NonZeroU32;
+#[cfg_ssr]
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+#[cfg_ssr]
+use std::time::Instant;
+
+#[cfg_ssr]
+use once_cell::sync::Lazy;
+
+use crate::*;
+
+/// Props for [`Cache`].
+#[derive(Props)]
+pub struct CacheProps {
+    /// Identifies the cached HTML. Every subtree that renders differently needs its own key, e.g.
+    /// `format!("nav-{locale}")`.
+    #[prop(setter(into))]
+    key: String,
+    /// How long the cached HTML can be reused for. If `None`, it is reused until it is evicted
+    /// from the cache or removed using [`SsrCache::remove`].
+    #[prop(default)]
+    ttl: Option<Duration>,
+    children: Children,
+}
+
+/// Caches the rendered HTML of its children in SSR mode, so that parts of the page that are the
+/// same for every request, e.g. headers and footers, are only rendered once.
+///
+/// The cache is shared by the whole process. If there is cached HTML for `key`, it is written
+/// into the output as is and the children are not created at all. This means that side effects
+/// of the children, e.g. head tags and server state, are only there when the HTML is rendered.
+///
+/// The children get their own hydration keys, just like a [`Suspense`](crate::Suspense)
+/// boundary, so that the cached HTML can be hydrated wherever it is rendered. Children with
+/// suspense or [`LazyHydrate`] boundaries are never cached since their keys depend on the rest of
+/// the page.
+///
+/// On the client, the children are hydrated as usual.
+///
+/// # Example
+/// ```
+/// # use sycamore::prelude::*;
+/// # use sycamore::web::Cache;
+/// # #[component] fn Navigation() -> View { view! {} }
+/// # fn App() -> View {
+/// view! {
+///     Cache(key="navigation") {
+///         Navigation {}
+///     }
+/// }
+/// # }
+/// ```
+#[component]
+pub fn Cache(props: CacheProps) -> View {
+    let CacheProps { key, ttl, children } = props;
+    is_ssr! {
+        let is_hydrating = IS_HYDRATING.get();
+        let boundary = boundary_key(&key).to_string();
+        if let Some(html) = CACHE.lock().unwrap().get(&key, is_hydrating) {
+            let html = html.to_string();
+            return view! {
+                sycamore-cache(
+                    data-key=boundary,
+                    style="display:contents",
+                    dangerously_set_inner_html=html,
+                )
+            };
+        }
+
+        let next_suspense_key = peek_suspense_key();
+        let children =
+            HydrationRegistry::in_suspense_scope(boundary_key(&key), move || children.call());
+        if peek_suspense_key() == next_suspense_key {
+            let mut html = String::new();
+            render_recursive_view(&children, &mut html);
+            CACHE.lock().unwrap().insert(key, is_hydrating, html, ttl);
+        }
+        view! {
+            sycamore-cache(data-key=boundary, style="display:contents") {
+                (children)
+            }
+        }
+    }
+    is_not_ssr! {
+        let _ = (key, ttl);
+        if !IS_HYDRATING.get() {
+            return children.call();
+        }
+        #[cfg(not(feature = "hydrate"))]
+        unreachable!("cannot hydrate without the `hydrate` feature");
+        #[cfg(feature = "hydrate")]
+        {
+            let view = view! { sycamore-cache() };
+            let element = view.nodes[0]
+                .as_web_sys()
+                .unchecked_ref::<web_sys::Element>()
+                .clone();
+            let boundary: NonZeroU32 = element
+                .get_attribute("data-key")
+                .unwrap()
+                .parse()
+                .unwrap();
+            hydrate_boundary(boundary, &element, children);
+            view
+        }
+    }
+}
+
+/// The suspense key that is used for the hydration keys of the children of a [`Cache`].
+///
+/// Unlike the keys of suspense boundaries, this only depends on the `key` of the cache so that
+/// the cached HTML stays valid wherever it is rendered. The highest bit is always set so that it
+/// never collides with the key of a suspense boundary.
+#[cfg_ssr]
+fn boundary_key(key: &str) -> std::num::NonZeroU32 {
+    // FNV-1a, which is stable across builds, unlike `DefaultHasher`.
+    let hash = key.bytes().fold(0x811c_9dc5_u32, |hash, byte| {
+        (hash ^ u32::from(byte)).wrapping_mul(0x0100_0193)
+    });
+    std::num::NonZeroU32::new(hash | 1 << 31).unwrap()
+}
+
+/// The default capacity of the [`SsrCache`] in bytes.
+#[cfg_ssr]
+const DEFAULT_CAPACITY: usize = 16 * 1024 * 1024;
+
+#[cfg_ssr]
+static CACHE: Lazy<Mutex<CacheState>> = Lazy::new(|| Mutex::new(CacheState::new(DEFAULT_CAPACITY)));
+
+#[cfg_ssr]
+struct CacheEntry {
+    html: Arc<str>,
+    expires_at: Option<Instant>,
+    /// The value of [`CacheState::clock`] when the entry was last used.
+    last_used: u64,
+}
+
+#[cfg_ssr]
+struct CacheState {
+    /// The entries by key and whether they were rendered with hydration keys.
+    entries: HashMap<(String, bool), CacheEntry>,
+    /// The total size of the entries in bytes.
+    size: usize,
+    capacity: usize,
+    /// Incremented every time an entry is used, for finding the least recently used entry.
+    clock: u64,
+}
+
+#[cfg_ssr]
+impl CacheState {
+    fn new(capacity: usize) -> Self {
+        Self {
+            entries: HashMap::new(),
+            size: 0,
+            capacity,
+            clock: 0,
+        }
+    }
+
+    fn get(&mut self, key: &str, is_hydrating: bool) -> Option<Arc<str>> {
+        let full_key = (key.to_string(), is_hydrating);
+        let entry = self.entries.get_mut(&full_key)?;
+        if entry
+            .expires_at
+            .is_some_and(|expires_at| expires_at <= Instant::now())
+        {
+            self.remove_entry(&full_key);
+            return None;
+        }
+        self.clock += 1;
+        entry.last_used = self.clock;
+        Some(Arc::clone(&entry.html))
+    }
+
+    fn insert(&mut self, key: String, is_hydrating: bool, html: String, ttl: Option<Duration>) {
+        let full_key = (key, is_hydrating);
+        self.remove_entry(&full_key);
+        let size = full_key.0.len() + html.len();
+        // Entries that do not fit are not cached at all instead of evicting everything else.
+        if size > self.capacity {
+            return;
+        }
+        self.clock += 1;
+        self.size += size;
+        self.entries.insert(
+            full_key,
+            CacheEntry {
+                html: html.into(),
+                expires_at: ttl.map(|ttl| Instant::now() + ttl),
+                last_used: self.clock,
+            },
+        );
+        self.evict();
+    }
+
+    /// Removes the least recently used entries until the cache fits into its capacity.
+    fn evict(&mut self) {
+        while self.size > self.capacity {
+            let key = self
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| key.clone())
+                .unwrap();
+            self.remove_entry(&key);
+        }
+    }
+
+    fn remove_entry(&mut self, key: &(String, bool)) {
+        if let Some(entry) = self.entries.remove(key) {
+            self.size -= key.0.len() + entry.html.len();
+        }
+    }
+}
+
+/// The process-wide cache that is used by [`Cache`] in SSR mode.
+///
+/// The functions of this type do nothing in DOM mode.
+#[derive(Debug)]
+pub struct SsrCache;
+
+impl SsrCache {
+    /// Sets the maximum total size of the cached HTML in bytes. The least recently used HTML is
+    /// evicted first. The default is 16 MiB.
+    pub fn set_capacity(capacity: usize) {
+        is_not_ssr! {
+            let _ = capacity;
+        }
+        is_ssr! {
+            let mut cache = CACHE.lock().unwrap();
+            cache.capacity = capacity;
+            cache.evict();
+        }
+    }
+
+    /// Removes the cached HTML for `key`, e.g. because the data it was rendered from changed.
+    pub fn remove(key: &str) {
+        is_not_ssr! {
+            let _ = key;
+        }
+        is_ssr! {
+            let mut cache = CACHE.lock().unwrap();
+            cache.remove_entry(&(key.to_string(), true));
+            cache.remove_entry(&(key.to_string(), false));
+        }
+    }
+
+    /// Removes all the cached HTML.
+    pub fn clear() {
+        is_ssr! {
+            let mut cache = CACHE.lock().unwrap();
+            cache.entries.clear();
+            cache.size = 0;
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg_ssr]
+mod tests {
+    use std::cell::Cell;
+
+    use expect_test::expect;
+
+    use super::*;
+
+    #[test]
+    fn cached_html_is_reused() {
+        thread_local! {
+            static RENDERS: Cell<u32> = const { Cell::new(0) };
+        }
+
+        #[component]
+        fn Navigation() -> View {
+            RENDERS.set(RENDERS.get() + 1);
+            view! { nav { a { "Home" } } }
+        }
+
+        let first = render_to_string(|| {
+            view! {
+                Cache(key="test-navigation") { Navigation {} }
+                p { "First" }
+            }
+        });
+        let second = render_to_string(|| {
+            view! {
+                p { "Second" }
+                Cache(key="test-navigation") { Navigation {} }
+            }
+        });
+        assert_eq!(RENDERS.get(), 1);
+        // The wrapper is hydrated as part of the page, the children with their own keys.
+        expect![[r#"<sycamore-cache data-key="3807796106" style="display:contents" data-hk="0.0"><nav data-hk="3807796106.0"><a data-hk="3807796106.1">Home</a></nav></sycamore-cache><p data-hk="0.1">First</p>"#]]
+            .assert_eq(&first);
+        expect![[r#"<p data-hk="0.0">Second</p><sycamore-cache data-key="3807796106" style="display:contents" data-hk="0.1"><nav data-hk="3807796106.0"><a data-hk="3807796106.1">Home</a></nav></sycamore-cache>"#]]
+            .assert_eq(&second);
+
+        SsrCache::remove("test-navigation");
+        let _ = render_to_string(|| view! { Cache(key="test-navigation") { Navigation {} } });
+        assert_eq!(RENDERS.get(), 2);
+    }
+
+    #[test]
+    fn expired_html_is_rendered_again() {
+        thread_local! {
+            static RENDERS: Cell<u32> = const { Cell::new(0) };
+        }
+
+        #[component]
+        fn Expired() -> View {
+            RENDERS.set(RENDERS.get() + 1);
+            view! { "Expired" }
+        }
+
+        for _ in 0..2 {
+            let _ = render_to_string(|| {
+                view! {
+                    Cache(key="test-expired", ttl=Duration::ZERO) { Expired {} }
+                }
+            });
+        }
+        assert_eq!(RENDERS.get(), 2);
+    }
+
+    #[test]
+    fn subtree_with_boundary_is_not_cached() {
+        let html = || {
+            render_to_string(|| {
+                view! {
+                    Cache(key="test-boundary") {
+                        LazyHydrate { "Lazy" }
+                    }
+                }
+            })
+        };
+        let _ = html();
+        assert!(CACHE.lock().unwrap().get("test-boundary", true).is_none());
+    }
+
+    #[test]
+    fn evict_least_recently_used() {
+        let mut cache = CacheState::new(12);
+        cache.insert("a".to_string(), true, "1234".to_string(), None);
+        cache.insert("b".to_string(), true, "1234".to_string(), None);
+        assert!(cache.get("a", true).is_some());
+        cache.insert("c".to_string(), true, "1234".to_string(), None);
+        assert!(cache.get("a", true).is_some());
+        assert!(cache.get("b", true).is_none());
+        assert!(cache.get("c", true).is_some());
+        assert_eq!(cache.size, 10);
+
+        cache.insert("d".to_string(), true, "1234567890123".to_string(), None);
+        assert!(cache.get("d", true).is_none());
+        assert_eq!(cache.size, 10);
+    }
+}
@@ -1125,20 +1125,35 @@ pub trait GlobalAttributes: SetAttribute + Sized {
     }
 
     /// Set JS property `name` with `value`.
-    fn prop(mut self, name: &'static str, value: impl Into<MaybeDyn<JsValue>>) -> Self {
-        self.set_attribute(name, value.into());
+    ///
+    /// In SSR mode, well-known properties such as `value`, `checked` and `selected` are rendered as
+    /// their attribute equivalents so that the server rendered HTML shows the initial state. Other
+    /// properties are only set on the client.
+    ///
+    /// `value` can be anything that implements [`IntoPropertyValue`]. Closures that return a
+    /// `JsValue` using `.into()` need an explicit return type, e.g. `move || -> JsValue { .. }`.
+    fn prop(mut self, name: &'static str, value: impl IntoPropertyValue) -> Self {
+        self.set_attribute(name, value.into_property_value());
         self
     }
 
     /// Set an event handler with `name`.
-    fn on<E: events::EventDescriptor, R>(
+    fn on<E: events::EventDescriptor, R>(self, event: E, handler: impl EventHandler<E, R>) -> Self {
+        self.on_with_options(event, events::EventOptions::default(), handler)
+    }
+
+    /// Set an event handler with `name` and the given [`EventOptions`](events::EventOptions).
+    ///
+    /// This is what `on:<event>|<modifier>` in the `view!` macro expands to.
+    fn on_with_options<E: events::EventDescriptor, R>(
         mut self,
         _: E,
+        options: events::EventOptions,
         mut handler: impl EventHandler<E, R>,
     ) -> Self {
         let scope = use_current_scope(); // Run handler inside the current scope.
         let handler = move |ev: web_sys::Event| scope.run_in(|| handler.call(ev.unchecked_into()));
-        self.set_event_handler(E::NAME, handler);
+        self.set_event_handler(E::NAME, options, handler);
         self
     }
 
@@ -1153,9 +1168,15 @@ pub trait GlobalAttributes: SetAttribute + Sized {
                 signal.set(E::CONVERT_FROM_JS(&value).expect("failed to convert value from js"));
             })
         };
-        self.set_event_handler(<E::Event as events::EventDescriptor>::NAME, handler);
+        self.set_event_handler(
+            <E::Event as events::EventDescriptor>::NAME,
+            events::EventOptions::default(),
+            handler,
+        );
 
-        self.prop(E::TARGET_PROPERTY, move || signal.get_clone().into())
+        self.prop(E::TARGET_PROPERTY, move || -> PropertyValue {
+            signal.get_clone().into()
+        })
     }
 }
 
@@ -0,0 +1,274 @@
+//! Components for catching errors.
+
+use std::error::Error;
+use std::fmt;
+
+use crate::*;
+
+/// An error that was thrown using [`throw_error`].
+#[derive(Clone)]
+pub struct ThrownError(Rc<dyn Error>);
+
+impl ThrownError {
+    /// Create a new [`ThrownError`] from any error or string.
+    pub fn new(err: impl Into<Box<dyn Error>>) -> Self {
+        Self(err.into().into())
+    }
+
+    /// Returns a reference to the inner error if it is of type `E`.
+    pub fn downcast_ref<E: Error + 'static>(&self) -> Option<&E> {
+        self.0.downcast_ref()
+    }
+}
+
+impl fmt::Display for ThrownError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl fmt::Debug for ThrownError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// Context provided by [`ErrorBoundary`] to its children.
+#[derive(Clone, Copy)]
+struct ErrorBoundaryContext {
+    error: Signal<Option<ThrownError>>,
+}
+
+/// Throws an error to the nearest [`ErrorBoundary`], which will then render its fallback instead
+/// of its children.
+///
+/// This can be called anywhere inside of the boundary, e.g. while rendering, inside effects or
+/// inside async components and resources. If there is no `ErrorBoundary` above the current scope,
+/// the error is logged to the console instead.
+///
+/// Note that this does not unwind: code after `throw_error` still runs.
+pub fn throw_error(err: impl Into<Box<dyn Error>>) {
+    let err = ThrownError::new(err);
+    match try_use_context::<ErrorBoundaryContext>() {
+        Some(ctx) => ctx.error.set(Some(err)),
+        None => console_error!("uncaught error: {err}"),
+    }
+}
+
+/// Passed to the fallback of an [`ErrorBoundary`]. Calling [`reset`](Self::reset) clears the
+/// error and renders the children again.
+#[derive(Clone, Copy)]
+pub struct ErrorBoundaryReset {
+    error: Signal<Option<ThrownError>>,
+}
+
+impl ErrorBoundaryReset {
+    /// Clears the error and shows the children of the [`ErrorBoundary`] again.
+    pub fn reset(self) {
+        self.error.set(None);
+    }
+}
+
+impl fmt::Debug for ErrorBoundaryReset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ErrorBoundaryReset").finish()
+    }
+}
+
+/// Props for [`ErrorBoundary`].
+#[derive(Props)]
+pub struct ErrorBoundaryProps {
+    /// Rendered instead of the children when an error is thrown.
+    #[prop(setter(transform = |f: impl Fn(ThrownError, ErrorBoundaryReset) -> View + 'static| Box::new(f) as Box<dyn Fn(ThrownError, ErrorBoundaryReset) -> View>))]
+    fallback: Box<dyn Fn(ThrownError, ErrorBoundaryReset) -> View>,
+    children: Children,
+}
+
+/// `ErrorBoundary` catches errors that are thrown using [`throw_error`] inside of its children and
+/// renders `fallback` instead. This works in both DOM and SSR mode. When streaming SSR, an error
+/// inside of a [`Suspense`] boundary streams the fallback like any other content.
+///
+/// The fallback is passed the error and an [`ErrorBoundaryReset`] which can be used to clear the
+/// error and show the children again. The children are only rendered once and are kept alive while
+/// the fallback is shown, so they should derive their content from signals that can be fixed before
+/// resetting. Errors thrown in the fallback itself are caught by the next `ErrorBoundary` up.
+///
+/// Only errors that are thrown with [`throw_error`] are caught. Panics are not caught since they
+/// cannot be recovered from on `wasm32`, where they abort the program.
+///
+/// # Example
+/// ```
+/// # use sycamore::prelude::*;
+/// # use sycamore::web::{throw_error, ErrorBoundary};
+/// #[component]
+/// fn Widget() -> View {
+///     throw_error("something went wrong");
+///     view! {}
+/// }
+///
+/// # fn App() -> View {
+/// view! {
+///     ErrorBoundary(
+///         fallback=|err, reset| view! {
+///             p { "Error: " (err.to_string()) }
+///             button(on:click=move |_| reset.reset()) { "Try again" }
+///         },
+///     ) {
+///         Widget {}
+///     }
+/// }
+/// # }
+/// ```
+#[component]
+pub fn ErrorBoundary(props: ErrorBoundaryProps) -> View {
+    let ErrorBoundaryProps { fallback, children } = props;
+
+    let error = create_signal(None::<ThrownError>);
+    let reset = ErrorBoundaryReset { error };
+    // Only the children should see the context so that errors in the fallback propagate to the
+    // next boundary up. The children are kept alive while the fallback is shown since the error
+    // might have been thrown from an effect inside of them that is still running.
+    let mut view = View::default();
+    let _ = create_child_scope(|| {
+        provide_context(ErrorBoundaryContext { error });
+        view = children.call();
+    });
+    let has_error = create_selector(move || error.with(Option::is_some));
+
+    view! {
+        Show(when=move || !has_error.get()) {
+            (view)
+        }
+        (match error.get_clone() {
+            Some(err) => fallback(err, reset),
+            None => View::default(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use super::*;
+
+    #[component(inline_props)]
+    fn Thrower(fail: Signal<bool>) -> View {
+        if fail.get_untracked() {
+            throw_error("failed");
+        }
+        view! { "ok" }
+    }
+
+    #[test]
+    fn error_boundary_renders_fallback() {
+        let html = render_to_string(|| {
+            let fail = create_signal(true);
+            view! {
+                ErrorBoundary(
+                    fallback=|err, _| view! { p { "error: " (err.to_string()) } },
+
+                ) {
+                    Thrower(fail=fail)
+                }
+            }
+        });
+        expect![[
+            r#"<!--/--><!--/--><!--/--><p data-hk="0.0">error: <!--/-->failed<!--/--></p><!--/-->"#
+        ]]
+        .assert_eq(&html);
+    }
+
+    #[test]
+    fn error_boundary_reset() {
+        let _ = create_root(|| {
+            let fail = create_signal(true);
+            let reset = create_signal(None::<ErrorBoundaryReset>);
+            let view = view! {
+                ErrorBoundary(
+                    fallback=move |_, r| {
+                        reset.set(Some(r));
+                        view! { "error" }
+                    },
+
+                ) {
+                    Thrower(fail=fail)
+                }
+            };
+            expect!["<!--/--><!--/--><!--/-->error<!--/-->"]
+                .assert_eq(&render_view_to_string(&view));
+
+            fail.set(false);
+            reset.get().unwrap().reset();
+            expect!["<!--/-->ok<!--/--><!--/--><!--/-->"].assert_eq(&render_view_to_string(&view));
+        });
+    }
+
+    #[test]
+    fn error_boundary_catches_errors_in_effects() {
+        let _ = create_root(|| {
+            let trigger = create_signal(false);
+            let view = view! {
+                ErrorBoundary(
+                    fallback=|err, _| view! { "error: " (err.to_string()) },
+                    children=Children::new(move || {
+                        create_effect(move || {
+                            if trigger.get() {
+                                throw_error("effect failed");
+                            }
+                        });
+                        view! { "ok" }
+                    }),
+                )
+            };
+            expect!["<!--/-->ok<!--/--><!--/--><!--/-->"].assert_eq(&render_view_to_string(&view));
+
+            trigger.set(true);
+            expect!["<!--/--><!--/--><!--/-->error: <!--/-->effect failed<!--/--><!--/-->"]
+                .assert_eq(&render_view_to_string(&view));
+        });
+    }
+
+    #[cfg(feature = "suspense")]
+    #[test]
+    fn error_boundary_streams_fallback_from_suspense() {
+        use std::time::Duration;
+
+        use futures::StreamExt;
+        use sycamore_futures::{sleep, TestExecutor};
+
+        #[component]
+        async fn AsyncThrower() -> View {
+            sleep(Duration::from_secs(1)).await;
+            throw_error("async failed");
+            view! { "unreachable" }
+        }
+
+        let executor = TestExecutor::new();
+        let stream = executor.enter(|| {
+            render_to_string_stream(|| {
+                view! {
+                    Suspense(fallback=|| "loading".into()) {
+                        ErrorBoundary(
+                            fallback=|err, _| view! { "error: " (err.to_string()) },
+                        ) {
+                            AsyncThrower {}
+                        }
+                    }
+                }
+            })
+        });
+        executor.advance_time(Duration::from_secs(1));
+        let fragments = executor.run(stream.collect::<Vec<_>>()).unwrap();
+        expect![[r#"
+            [
+                "<template id=\"sycamore-suspense-1\"><!--/--><!--/--><!--/--><!--/-->error: <!--/-->async failed<!--/--><!--/--><!--/--></template><script>__sycamore_suspense(1)</script>",
+            ]
+        "#]].assert_debug_eq(&&fragments[1..]);
+    }
+
+    #[test]
+    fn throw_error_without_boundary_does_not_panic() {
+        let _ = create_root(|| throw_error("failed"));
+    }
+}
@@ -1,7 +1,13 @@
 //! Definition for all the events that can be listened to.
 
+use std::cell::Cell;
 #[cfg(feature = "suspense")]
-use sycamore_futures::spawn_local_scoped;
+use std::convert::Infallible;
+
+#[cfg(feature = "suspense")]
+use sycamore_futures::{create_action, spawn_local_scoped, Action, ConcurrencyPolicy};
+#[cfg(feature = "suspense")]
+use sycamore_reactive::{create_selector, ReadSignal};
 use wasm_bindgen::JsCast;
 pub use web_sys::{
     AnimationEvent, BeforeUnloadEvent, CompositionEvent, DeviceMotionEvent, DeviceOrientationEvent,
@@ -195,6 +201,150 @@ where
     }
 }
 
+/// Options for how an event listener is attached and how the event is handled before it reaches
+/// the event handler.
+///
+/// In the `view!` macro, these can be set using modifiers on the `on:` directive, e.g.
+/// `on:submit|prevent=handler`. The available modifiers are `prevent`, `stop`, `once`, `capture`,
+/// `passive` and `self`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct EventOptions {
+    pub(crate) prevent_default: bool,
+    pub(crate) stop_propagation: bool,
+    pub(crate) once: bool,
+    pub(crate) capture: bool,
+    pub(crate) passive: bool,
+    pub(crate) self_only: bool,
+}
+
+impl EventOptions {
+    /// Creates a new [`EventOptions`] with all options disabled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Call `preventDefault()` on the event before running the handler.
+    pub fn prevent_default(mut self) -> Self {
+        self.prevent_default = true;
+        self
+    }
+
+    /// Call `stopPropagation()` on the event before running the handler.
+    pub fn stop_propagation(mut self) -> Self {
+        self.stop_propagation = true;
+        self
+    }
+
+    /// Remove the event listener after it has been called once.
+    pub fn once(mut self) -> Self {
+        self.once = true;
+        self
+    }
+
+    /// Listen to the event in the capture phase instead of the bubbling phase.
+    pub fn capture(mut self) -> Self {
+        self.capture = true;
+        self
+    }
+
+    /// Indicate to the browser that the handler never calls `preventDefault()`. This allows the
+    /// browser to e.g. start scrolling without waiting for the handler.
+    pub fn passive(mut self) -> Self {
+        self.passive = true;
+        self
+    }
+
+    /// Only run the handler if the event was dispatched on the element itself and not on one of
+    /// its descendants.
+    pub fn self_only(mut self) -> Self {
+        self.self_only = true;
+        self
+    }
+}
+
+#[sycamore_macro::cfg_not_ssr]
+impl EventOptions {
+    /// Returns `true` if any of the options that are passed to `addEventListener` are set.
+    pub(crate) fn has_listener_options(&self) -> bool {
+        self.once || self.capture || self.passive
+    }
+
+    /// Wraps `handler` so that the event is filtered and modified according to these options
+    /// before it is called.
+    pub(crate) fn wrap_handler(
+        self,
+        mut handler: impl FnMut(Event) + 'static,
+    ) -> impl FnMut(Event) + 'static {
+        move |ev: Event| {
+            if self.self_only && ev.target() != ev.current_target() {
+                return;
+            }
+            if self.prevent_default {
+                ev.prevent_default();
+            }
+            if self.stop_propagation {
+                ev.stop_propagation();
+            }
+            handler(ev);
+        }
+    }
+}
+
+/// The events that are handled by a single listener on the render root when event delegation is
+/// enabled using [`set_delegation`]. All of these events bubble.
+pub const DELEGATED_EVENTS: &[&str] = &[
+    "beforeinput",
+    "click",
+    "contextmenu",
+    "dblclick",
+    "focusin",
+    "focusout",
+    "input",
+    "keydown",
+    "keyup",
+    "mousedown",
+    "mousemove",
+    "mouseout",
+    "mouseover",
+    "mouseup",
+    "pointerdown",
+    "pointermove",
+    "pointerout",
+    "pointerover",
+    "pointerup",
+    "touchend",
+    "touchmove",
+    "touchstart",
+];
+
+thread_local! {
+    static DELEGATION_ENABLED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Enables or disables event delegation for all views that are rendered on the current thread
+/// afterwards. Event delegation is disabled by default.
+///
+/// Instead of attaching a separate event listener to every element, a single listener for each
+/// of the [`DELEGATED_EVENTS`] is attached to the node that the view is rendered into (e.g. with
+/// [`render_to`](crate::render_to)). When the event fires, it is dispatched to the handlers of
+/// every element from the event target up to the root, stopping early if a handler calls
+/// `stop_propagation()`. This makes creating and destroying large lists of elements with event
+/// handlers much cheaper.
+///
+/// Handlers with the `once`, `capture` or `passive` options and events that are not in
+/// [`DELEGATED_EVENTS`] still get their own event listener.
+///
+/// This should generally be called once at the start of the program, before anything is
+/// rendered.
+pub fn set_delegation(enabled: bool) {
+    DELEGATION_ENABLED.set(enabled);
+}
+
+/// Returns whether event delegation has been enabled using [`set_delegation`].
+pub fn is_delegation_enabled() -> bool {
+    DELEGATION_ENABLED.get()
+}
+
 /// Marker trait to workaround specialization.
 #[doc(hidden)]
 #[cfg(feature = "suspense")]
@@ -212,3 +362,140 @@ where
         spawn_local_scoped(self(event));
     }
 }
+
+/// An async event handler that is run according to a [`ConcurrencyPolicy`]. Created using
+/// [`async_handler`].
+///
+/// Unlike a plain async closure which spawns a new task for every event, this can be used to make
+/// sure that e.g. double-clicking a submit button does not send two requests.
+#[cfg(feature = "suspense")]
+pub struct AsyncEventHandler<Ev: 'static> {
+    action: Action<Ev, (), Infallible>,
+    is_running: ReadSignal<bool>,
+}
+
+#[cfg(feature = "suspense")]
+impl<Ev> Clone for AsyncEventHandler<Ev> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+#[cfg(feature = "suspense")]
+impl<Ev> Copy for AsyncEventHandler<Ev> {}
+
+#[cfg(feature = "suspense")]
+impl<Ev> AsyncEventHandler<Ev> {
+    /// Returns a signal that is `true` while the handler is running. This is useful for disabling
+    /// a button while its handler is running.
+    pub fn is_running(&self) -> ReadSignal<bool> {
+        self.is_running
+    }
+
+    /// Aborts all the invocations of the handler that are currently running and clears the queue.
+    pub fn cancel(&self) {
+        self.action.cancel();
+    }
+}
+
+#[cfg(feature = "suspense")]
+impl<Ev> std::fmt::Debug for AsyncEventHandler<Ev> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AsyncEventHandler")
+            .field("action", &self.action)
+            .finish()
+    }
+}
+
+/// Marker trait to workaround specialization.
+#[doc(hidden)]
+#[cfg(feature = "suspense")]
+pub struct PolicyHandler;
+
+#[cfg(feature = "suspense")]
+impl<E: EventDescriptor> EventHandler<E, PolicyHandler> for AsyncEventHandler<E::EventTy> {
+    fn call(&mut self, event: E::EventTy) {
+        self.action.dispatch(event);
+    }
+}
+
+/// Creates an async event handler that is run according to `policy` when the event is fired
+/// while a previous invocation is still running.
+///
+/// # Example
+/// ```
+/// # use sycamore::prelude::*;
+/// # use sycamore::web::events::async_handler;
+/// # use sycamore_futures::ConcurrencyPolicy;
+/// # async fn submit_form() {}
+/// # fn App() -> View {
+/// let on_submit = async_handler(ConcurrencyPolicy::IgnoreWhilePending, |_| async move {
+///     submit_form().await;
+/// });
+/// let is_running = on_submit.is_running();
+/// view! {
+///     button(on:click=on_submit, disabled=move || is_running.get()) { "Submit" }
+/// }
+/// # }
+/// ```
+#[cfg(feature = "suspense")]
+#[track_caller]
+pub fn async_handler<Ev, F, Fut>(policy: ConcurrencyPolicy, mut f: F) -> AsyncEventHandler<Ev>
+where
+    F: FnMut(Ev) -> Fut + 'static,
+    Fut: std::future::Future<Output = ()> + 'static,
+{
+    let action = create_action(move |event| {
+        let fut = f(event);
+        async move {
+            fut.await;
+            Ok(())
+        }
+    })
+    .with_policy(policy);
+    AsyncEventHandler {
+        action,
+        is_running: create_selector(move || action.pending()),
+    }
+}
+
+#[cfg(all(test, feature = "suspense"))]
+mod tests {
+    use std::time::Duration;
+
+    use sycamore_futures::{sleep, with_test_executor};
+    use sycamore_reactive::*;
+
+    use super::*;
+
+    #[test]
+    fn async_handler_policy() {
+        with_test_executor(|executor| {
+            let count = create_signal(0);
+            let mut handler = async_handler(
+                ConcurrencyPolicy::IgnoreWhilePending,
+                move |_: web_sys::MouseEvent| async move {
+                    sleep(Duration::from_secs(1)).await;
+                    count.update(|count| *count += 1);
+                },
+            );
+            let is_running = handler.is_running();
+            assert!(!is_running.get());
+
+            // Fire the event the same way an `on:click` listener would. The event data is never
+            // read so a placeholder value can be used outside of the browser.
+            let mut fire = || {
+                EventHandler::<click, _>::call(
+                    &mut handler,
+                    wasm_bindgen::JsValue::UNDEFINED.unchecked_into(),
+                )
+            };
+            fire();
+            fire();
+
+            assert!(is_running.get());
+            executor.advance_time(Duration::from_secs(1));
+            assert!(!is_running.get());
+            assert_eq!(count.get(), 1);
+        });
+    }
+}
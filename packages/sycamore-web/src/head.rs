@@ -0,0 +1,586 @@
+//! Components for managing the document `<head>`.
+//!
+//! In DOM mode, the tags are inserted directly into `document.head`. In SSR mode, they are
+//! rendered into the `head` element of the view, if there is one.
+
+use crate::*;
+
+/// The attribute that is used to find the tags that are managed by the head components.
+const HEAD_KEY_ATTR: &str = "data-sycamore-head";
+
+thread_local! {
+    static NEXT_ID: Cell<usize> = const { Cell::new(0) };
+}
+
+/// A tag that was added to the head by one of the head components.
+#[derive(Clone, PartialEq)]
+pub(crate) struct HeadTag {
+    id: usize,
+    /// Only one tag is rendered for each key. If there are several, the one that was added last
+    /// wins.
+    key: Cow<'static, str>,
+    tag: &'static str,
+    attributes: Vec<(&'static str, Cow<'static, str>)>,
+    content: Option<Cow<'static, str>>,
+}
+
+/// Returns the tags that should be rendered, i.e. the last tag for each key.
+#[cfg_ssr]
+fn active_tags(tags: &[HeadTag]) -> impl Iterator<Item = &HeadTag> {
+    tags.iter()
+        .enumerate()
+        .filter(|(i, tag)| !tags[i + 1..].iter().any(|other| other.key == tag.key))
+        .map(|(_, tag)| tag)
+}
+
+/// The tags that are rendered into the `head` element in SSR mode.
+///
+/// This is provided by the SSR render functions.
+#[cfg_ssr]
+#[derive(Clone, Copy)]
+pub(crate) struct HeadTags {
+    tags: Signal<Vec<HeadTag>>,
+}
+
+#[cfg_ssr]
+impl HeadTags {
+    pub fn new() -> Self {
+        Self {
+            tags: create_signal(Vec::new()),
+        }
+    }
+
+    fn find() -> Option<Self> {
+        try_use_context()
+    }
+
+    fn update(self, f: impl FnOnce(&mut Vec<HeadTag>)) {
+        // The tags might already be gone if the whole render is being disposed.
+        if self.tags.is_alive() {
+            self.tags.update(f);
+        }
+    }
+
+    /// Renders the current tags to HTML.
+    pub fn render_to_string(self) -> String {
+        let mut buf = String::new();
+        self.tags.with(|tags| {
+            for tag in active_tags(tags) {
+                render_recursive(&tag.to_ssr_node(), &mut buf);
+            }
+        });
+        buf
+    }
+
+    /// Creates a node that renders the current tags. This is appended to the `head` element.
+    pub fn create_node(self) -> SsrNode {
+        use std::sync::{Arc, Mutex};
+
+        let view = Arc::new(Mutex::new(View::new()));
+        create_effect({
+            let view = view.clone();
+            move || {
+                let nodes = self
+                    .tags
+                    .with(|tags| active_tags(tags).map(HeadTag::to_ssr_node).collect());
+                *view.lock().unwrap() = View::from_nodes(nodes);
+            }
+        });
+        SsrNode::Dynamic { view }
+    }
+}
+
+#[cfg_ssr]
+impl HeadTag {
+    fn to_ssr_node(&self) -> SsrNode {
+        let mut attributes = vec![(HEAD_KEY_ATTR.into(), self.key.clone())];
+        attributes.extend(
+            self.attributes
+                .iter()
+                .map(|(name, value)| (Cow::Borrowed(*name), value.clone())),
+        );
+        // Scripts are not HTML-escaped since the browser does not decode them. Only `</` is
+        // escaped so that the content cannot close the `<script>` tag.
+        let (children, inner_html) = match &self.content {
+            Some(content) if self.tag == "script" => (
+                Vec::new(),
+                Some(Box::new(content.replace("</", "<\\/").into())),
+            ),
+            Some(content) => (
+                vec![SsrNode::TextStatic {
+                    text: content.clone(),
+                }],
+                None,
+            ),
+            None => (Vec::new(), None),
+        };
+        SsrNode::Element {
+            tag: self.tag.into(),
+            attributes,
+            bool_attributes: Vec::new(),
+            children,
+            inner_html,
+            hk_key: None,
+        }
+    }
+}
+
+#[cfg_not_ssr]
+thread_local! {
+    static HEAD_TAGS: std::cell::RefCell<Vec<HeadTag>> = const { std::cell::RefCell::new(Vec::new()) };
+}
+
+/// The tags in `document.head` in DOM mode. Since there is only one document, the tags are stored
+/// globally.
+#[cfg_not_ssr]
+#[derive(Clone, Copy)]
+struct HeadTags;
+
+#[cfg_not_ssr]
+impl HeadTags {
+    fn find() -> Option<Self> {
+        Some(Self)
+    }
+
+    fn update(self, f: impl FnOnce(&mut Vec<HeadTag>)) {
+        let mut changed = HEAD_TAGS.with(|tags| {
+            let mut tags = tags.borrow_mut();
+            let before = tags.clone();
+            f(&mut tags);
+            before
+                .iter()
+                .filter(|tag| !tags.contains(tag))
+                .chain(tags.iter().filter(|tag| !before.contains(tag)))
+                .map(|tag| tag.key.clone())
+                .collect::<Vec<_>>()
+        });
+        changed.dedup();
+        for key in changed {
+            sync_head_tag(&key);
+        }
+    }
+}
+
+/// Updates the element for `key` in `document.head` to match the last tag with that key, or
+/// removes it if there is none.
+///
+/// Elements that were rendered on the server are reused.
+#[cfg_not_ssr]
+fn sync_head_tag(key: &str) {
+    let head = document().head().expect("document has no `<head>`");
+    let mut existing = None;
+    let mut child = head.first_element_child();
+    while let Some(el) = child {
+        // The title might not have been created by us, e.g. if it is in `index.html`.
+        if el.get_attribute(HEAD_KEY_ATTR).as_deref() == Some(key)
+            || (key == "title" && el.tag_name().eq_ignore_ascii_case("title"))
+        {
+            existing = Some(el);
+            break;
+        }
+        child = el.next_element_sibling();
+    }
+
+    HEAD_TAGS.with(|tags| {
+        let tags = tags.borrow();
+        let Some(tag) = tags.iter().rev().find(|tag| tag.key == key) else {
+            if let Some(el) = existing {
+                el.remove();
+            }
+            return;
+        };
+
+        let el = match existing {
+            Some(el) if el.tag_name().eq_ignore_ascii_case(tag.tag) => el,
+            existing => {
+                if let Some(existing) = existing {
+                    existing.remove();
+                }
+                let el = document().create_element(tag.tag).unwrap_throw();
+                head.append_child(&el).unwrap_throw();
+                el
+            }
+        };
+        for name in el.get_attribute_names().iter() {
+            let name = name.as_string().unwrap_throw();
+            if name != HEAD_KEY_ATTR && !tag.attributes.iter().any(|(n, _)| *n == name) {
+                el.remove_attribute(&name).unwrap_throw();
+            }
+        }
+        for (name, value) in &tag.attributes {
+            if el.get_attribute(name).as_deref() != Some(value) {
+                el.set_attribute(name, value).unwrap_throw();
+            }
+        }
+        el.set_attribute(HEAD_KEY_ATTR, key).unwrap_throw();
+        if let Some(content) = &tag.content {
+            if el.text_content().as_deref() != Some(content) {
+                el.set_text_content(Some(content));
+            }
+        }
+    });
+}
+
+/// Returns a key for a tag that does not have one, derived from its attributes and content.
+///
+/// This uses FNV-1a rather than the standard library hasher so that the server and the client
+/// always agree on the key, and the same page always renders the same HTML.
+fn content_key(
+    tag: &str,
+    attributes: &[(&'static str, Cow<'static, str>)],
+    content: Option<&str>,
+) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    let mut write = |bytes: &[u8]| {
+        for byte in bytes.iter().chain([&0]) {
+            hash = (hash ^ u64::from(*byte)).wrapping_mul(0x100000001b3);
+        }
+    };
+    for (name, value) in attributes {
+        write(name.as_bytes());
+        write(value.as_bytes());
+    }
+    write(content.unwrap_or_default().as_bytes());
+    format!("{tag}:{hash:016x}")
+}
+
+/// Adds a tag to the head until the current scope is disposed.
+///
+/// If `key` is `None`, a key is derived from the attributes and the initial content. Identical
+/// tags without a key are therefore only rendered once.
+fn use_head_tag(
+    key: Option<Cow<'static, str>>,
+    tag: &'static str,
+    attributes: Vec<(&'static str, Cow<'static, str>)>,
+    content: Option<MaybeDyn<Cow<'static, str>>>,
+) {
+    let Some(head) = HeadTags::find() else {
+        return;
+    };
+    let id = NEXT_ID.with(|next| next.replace(next.get() + 1));
+    let key = key.unwrap_or_else(|| {
+        let content = content
+            .as_ref()
+            .map(|content| untrack(|| content.get_clone()));
+        content_key(tag, &attributes, content.as_deref()).into()
+    });
+
+    create_effect({
+        let key = key.clone();
+        move || {
+            let new = HeadTag {
+                id,
+                key: key.clone(),
+                tag,
+                attributes: attributes.clone(),
+                content: content.as_ref().map(MaybeDyn::get_clone),
+            };
+            head.update(|tags| match tags.iter_mut().find(|tag| tag.id == id) {
+                Some(tag) => *tag = new,
+                None => tags.push(new),
+            });
+        }
+    });
+    on_cleanup(move || head.update(|tags| tags.retain(|tag| tag.id != id)));
+}
+
+/// Collects the attributes that are set.
+fn collect_attributes<const N: usize>(
+    attributes: [(&'static str, Option<Cow<'static, str>>); N],
+) -> Vec<(&'static str, Cow<'static, str>)> {
+    attributes
+        .into_iter()
+        .filter_map(|(name, value)| Some((name, value?)))
+        .collect()
+}
+
+/// Props for [`Title`].
+#[derive(Props)]
+pub struct TitleProps {
+    /// The title of the document. This can be dynamic.
+    #[prop(setter(into))]
+    text: MaybeDyn<Cow<'static, str>>,
+}
+
+/// Sets the `<title>` of the document while this component is mounted.
+///
+/// If there are several `Title`s, the one that was rendered last wins. Since children are rendered
+/// after their parents, this is usually the innermost one.
+///
+/// # Example
+/// ```
+/// # use sycamore::prelude::*;
+/// # use sycamore::web::Title;
+/// # fn App() -> View {
+/// let count = create_signal(0);
+/// view! {
+///     Title(text=move || format!("{} unread messages", count.get()))
+/// }
+/// # }
+/// ```
+#[component]
+pub fn Title(props: TitleProps) -> View {
+    use_head_tag(Some("title".into()), "title", Vec::new(), Some(props.text));
+    View::default()
+}
+
+/// Props for [`Meta`].
+#[derive(Props)]
+pub struct MetaProps {
+    #[prop(setter(into))]
+    name: Option<Cow<'static, str>>,
+    #[prop(setter(into))]
+    property: Option<Cow<'static, str>>,
+    #[prop(setter(into))]
+    http_equiv: Option<Cow<'static, str>>,
+    #[prop(setter(into))]
+    charset: Option<Cow<'static, str>>,
+    #[prop(setter(into))]
+    content: Option<Cow<'static, str>>,
+    /// Used to deduplicate the tag. Defaults to the `name`, `property`, `http-equiv` or `charset`
+    /// attribute.
+    #[prop(setter(into))]
+    key: Option<Cow<'static, str>>,
+}
+
+/// Adds a `<meta>` tag to the document head while this component is mounted.
+///
+/// Tags with the same key replace each other. The one that was rendered last wins.
+///
+/// # Example
+/// ```
+/// # use sycamore::prelude::*;
+/// # use sycamore::web::Meta;
+/// # fn App() -> View {
+/// view! {
+///     Meta(name="description", content="A page about Sycamore.")
+///     Meta(property="og:title", content="Sycamore")
+/// }
+/// # }
+/// ```
+#[component]
+pub fn Meta(props: MetaProps) -> View {
+    let key = props.key.or_else(|| {
+        [
+            ("name", &props.name),
+            ("property", &props.property),
+            ("http-equiv", &props.http_equiv),
+        ]
+        .into_iter()
+        .find_map(|(name, value)| Some(format!("meta:{name}:{}", value.as_ref()?).into()))
+        .or_else(|| props.charset.as_ref().map(|_| "meta:charset".into()))
+    });
+    let attributes = collect_attributes([
+        ("name", props.name),
+        ("property", props.property),
+        ("http-equiv", props.http_equiv),
+        ("charset", props.charset),
+        ("content", props.content),
+    ]);
+    use_head_tag(key, "meta", attributes, None);
+    View::default()
+}
+
+/// Props for [`Link`].
+#[derive(Props)]
+pub struct LinkProps {
+    #[prop(setter(into))]
+    rel: Option<Cow<'static, str>>,
+    #[prop(setter(into))]
+    href: Option<Cow<'static, str>>,
+    #[prop(setter(into))]
+    hreflang: Option<Cow<'static, str>>,
+    #[prop(setter(into))]
+    media: Option<Cow<'static, str>>,
+    #[prop(setter(into))]
+    sizes: Option<Cow<'static, str>>,
+    /// Used to deduplicate the tag. Defaults to the `rel` and `href` attributes.
+    #[prop(setter(into))]
+    key: Option<Cow<'static, str>>,
+}
+
+/// Adds a `<link>` tag to the document head while this component is mounted.
+///
+/// Tags with the same key replace each other. The one that was rendered last wins. To only have
+/// one tag for a `rel`, e.g. `canonical`, set the `key` explicitly.
+///
+/// # Example
+/// ```
+/// # use sycamore::prelude::*;
+/// # use sycamore::web::Link;
+/// # fn App() -> View {
+/// view! {
+///     Link(rel="canonical", href="https://sycamore.dev", key="canonical")
+/// }
+/// # }
+/// ```
+#[component]
+pub fn Link(props: LinkProps) -> View {
+    let key = props.key.or_else(|| {
+        Some(
+            format!(
+                "link:{}:{}",
+                props.rel.as_deref().unwrap_or_default(),
+                props.href.as_deref()?
+            )
+            .into(),
+        )
+    });
+    let attributes = collect_attributes([
+        ("rel", props.rel),
+        ("href", props.href),
+        ("hreflang", props.hreflang),
+        ("media", props.media),
+        ("sizes", props.sizes),
+    ]);
+    use_head_tag(key, "link", attributes, None);
+    View::default()
+}
+
+/// Props for [`Script`].
+#[derive(Props)]
+pub struct ScriptProps {
+    #[prop(setter(into))]
+    src: Option<Cow<'static, str>>,
+    #[prop(setter(into))]
+    r#type: Option<Cow<'static, str>>,
+    #[prop(default)]
+    r#async: bool,
+    #[prop(default)]
+    defer: bool,
+    /// The inline source of the script. Apart from `</`, which is escaped as `<\/` so that the
+    /// content cannot close the tag, this is not escaped, so never pass user input to it.
+    #[prop(setter(into))]
+    content: Option<Cow<'static, str>>,
+    /// Used to deduplicate the tag. Defaults to the `src` attribute.
+    #[prop(setter(into))]
+    key: Option<Cow<'static, str>>,
+}
+
+/// Adds a `<script>` tag to the document head while this component is mounted.
+///
+/// Tags with the same key replace each other. The one that was rendered last wins.
+///
+/// # Example
+/// ```
+/// # use sycamore::prelude::*;
+/// # use sycamore::web::Script;
+/// # fn App() -> View {
+/// view! {
+///     Script(
+///         r#type="application/ld+json",
+///         content=r#"{"@context": "https://schema.org", "@type": "WebSite"}"#,
+///     )
+/// }
+/// # }
+/// ```
+#[component]
+pub fn Script(props: ScriptProps) -> View {
+    let key = props
+        .key
+        .or_else(|| Some(format!("script:{}", props.src.as_ref()?).into()));
+    let attributes = collect_attributes([
+        ("src", props.src),
+        ("type", props.r#type),
+        ("async", props.r#async.then_some("".into())),
+        ("defer", props.defer.then_some("".into())),
+    ]);
+    use_head_tag(key, "script", attributes, props.content.map(Into::into));
+    View::default()
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use super::*;
+
+    #[test]
+    fn head_tags_are_rendered_into_head() {
+        let html = render_to_string(|| {
+            view! {
+                html {
+                    head {
+                        meta("charset"="utf-8")
+                    }
+                    body {
+                        Title(text="Site")
+                        Meta(name="description", content="site")
+                        div {
+                            Title(text="Page")
+                            Meta(name="description", content="page")
+                            Link(rel="stylesheet", href="/style.css")
+                            Script(r#type="application/ld+json", content="{\"a\": \"<b>\"}")
+                        }
+                    }
+                }
+            }
+        });
+        expect![[r#"<html data-hk="0.0"><head data-hk="0.1"><title data-sycamore-head="title">Page</title><meta data-sycamore-head="meta:name:description" name="description" content="page"><link data-sycamore-head="link:stylesheet:/style.css" rel="stylesheet" href="/style.css"><script data-sycamore-head="script:f3b5f55bc1962c46" type="application/ld+json">{"a": "<b>"}</script><meta charset="utf-8" data-hk="0.2"></head><body data-hk="0.3"><div data-hk="0.4"></div></body></html>"#]]
+            .assert_eq(&html);
+    }
+
+    #[test]
+    fn keyless_tags_are_deterministic() {
+        let render = || {
+            render_to_string(|| {
+                view! {
+                    html {
+                        head {}
+                        body {
+                            Script(content="let s = '</script>';")
+                        }
+                    }
+                }
+            })
+        };
+        let html = render();
+        expect![[r#"<html data-hk="0.0"><head data-hk="0.1"><script data-sycamore-head="script:d4095e6489d984df">let s = '<\/script>';</script></head><body data-hk="0.2"></body></html>"#]].assert_eq(&html);
+        assert_eq!(html, render());
+    }
+
+    #[test]
+    fn head_tags_are_removed_on_cleanup() {
+        let _ = create_root(|| {
+            provide_context(HeadTags::new());
+            let show = create_signal(true);
+            let view = view! {
+                head {}
+                Title(text="Site")
+                (if show.get() {
+                    view! { Title(text="Page") }
+                } else {
+                    view! {}
+                })
+            };
+            expect![[
+                r#"<head><title data-sycamore-head="title">Page</title></head><!--/--><!--/-->"#
+            ]]
+            .assert_eq(&render_view_to_string(&view));
+
+            show.set(false);
+            expect![[
+                r#"<head><title data-sycamore-head="title">Site</title></head><!--/--><!--/-->"#
+            ]]
+            .assert_eq(&render_view_to_string(&view));
+        });
+    }
+
+    #[test]
+    fn dynamic_title() {
+        let _ = create_root(|| {
+            provide_context(HeadTags::new());
+            let count = create_signal(0);
+            let view = view! {
+                head {}
+                Title(text=move || format!("count: {}", count.get()))
+            };
+            expect![[r#"<head><title data-sycamore-head="title">count: 0</title></head>"#]]
+                .assert_eq(&render_view_to_string(&view));
+
+            count.set(1);
+            expect![[r#"<head><title data-sycamore-head="title">count: 1</title></head>"#]]
+                .assert_eq(&render_view_to_string(&view));
+        });
+    }
+}
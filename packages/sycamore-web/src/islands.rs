@@ -0,0 +1,225 @@
+//! Islands architecture: pages that are static HTML except for a few interactive islands.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use sycamore_core::{Component, Props};
+
+use crate::*;
+
+/// The tag of the custom element that an island is rendered into.
+#[cfg_not_ssr]
+const ISLAND_TAG: &str = "sycamore-island";
+
+/// Context that is provided inside of an island. Islands inside of another island are rendered
+/// as normal components since the outer island already hydrates them.
+#[derive(Clone, Copy)]
+struct IslandContext;
+
+/// Creates an island. This is used by the `#[island]` macro and should not be called directly.
+///
+/// In SSR mode, the island is rendered into a `<sycamore-island>` element that holds the `name`
+/// of the island and the serialized `props`. The children are rendered with their own hydration
+/// keys so that every island can be hydrated on its own using [`Islands`].
+#[doc(hidden)]
+pub fn create_island<P: Serialize>(
+    name: &'static str,
+    props: P,
+    f: impl FnOnce(P) -> View,
+) -> View {
+    if try_use_context::<IslandContext>().is_some() {
+        return f(props);
+    }
+    is_not_ssr! {
+        let _ = name;
+        // We are not hydrating the island, e.g. the island was created after navigating to a new
+        // page. Just render it like any other component.
+        f(props)
+    }
+    is_ssr! {
+        let data = serde_json::to_string(&props).expect("could not serialize island props");
+        let is_hydrating = IS_HYDRATING.replace(true);
+        let mut children = View::default();
+        create_child_scope(|| {
+            provide_context(IslandContext);
+            provide_context(HydrationRegistry::new());
+            children = f(props);
+        });
+        // The island element itself is not hydrated.
+        IS_HYDRATING.set(false);
+        let view = view! {
+            sycamore-island(data-island=name, data-props=data) { (children) }
+        };
+        IS_HYDRATING.set(is_hydrating);
+        view
+    }
+}
+
+/// Function that deserializes the props of an island and renders it.
+type IslandFn = Box<dyn Fn(&str) -> View>;
+
+/// A registry of the islands of an app, used for hydrating them on the client.
+///
+/// Every island is hydrated on its own, so the rest of the page is never hydrated. Islands are
+/// found by the name of the component, which is why every island needs to be registered using
+/// [`register`](Self::register).
+///
+/// # Example
+/// ```no_run
+/// # use sycamore::prelude::*;
+/// # use sycamore::web::Islands;
+/// # #[derive(Props, serde::Serialize, serde::Deserialize)]
+/// # struct CounterProps { initial: i32 }
+/// #[island]
+/// fn Counter(props: CounterProps) -> View {
+///     let mut count = create_signal(props.initial);
+///     view! {
+///         button(on:click=move |_| count += 1) { (count) }
+///     }
+/// }
+///
+/// // On the client:
+/// Islands::new().register("Counter", Counter).hydrate();
+/// ```
+#[derive(Default)]
+pub struct Islands {
+    islands: HashMap<&'static str, IslandFn>,
+}
+
+impl Islands {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers an island. `name` must be the name of the component function.
+    pub fn register<P, S>(
+        mut self,
+        name: &'static str,
+        island: impl Component<P, View, S> + Clone + 'static,
+    ) -> Self
+    where
+        P: Props + DeserializeOwned,
+    {
+        self.islands.insert(
+            name,
+            Box::new(move |data| {
+                let props: P = serde_json::from_str(data)
+                    .unwrap_or_else(|err| panic!("could not deserialize props of `{name}`: {err}"));
+                let island = island.clone();
+                sycamore_core::component_scope(move || island.create(props))
+            }),
+        );
+        self
+    }
+
+    /// Hydrates all the islands in the document.
+    ///
+    /// Returns the reactive root of the islands. Disposing it cleans up all of them.
+    pub fn hydrate(self) -> RootHandle {
+        self.hydrate_to(&document())
+    }
+
+    /// Hydrates all the islands under `parent`.
+    ///
+    /// Returns the reactive root of the islands. Disposing it cleans up all of them.
+    pub fn hydrate_to(self, parent: &web_sys::Node) -> RootHandle {
+        create_root(|| {
+            is_ssr! {
+                let _ = parent;
+                panic!("`Islands::hydrate_to` is not available in SSR mode");
+            }
+            is_not_ssr! {
+                let elements = if let Some(parent) = parent.dyn_ref::<web_sys::Element>() {
+                    parent.query_selector_all(ISLAND_TAG)
+                } else {
+                    parent.unchecked_ref::<web_sys::Document>().query_selector_all(ISLAND_TAG)
+                }
+                .unwrap();
+                for i in 0..elements.length() {
+                    let element = elements.get(i).unwrap().unchecked_into::<web_sys::Element>();
+                    let name = element.get_attribute("data-island").unwrap_or_default();
+                    let Some(island) = self.islands.get(name.as_str()) else {
+                        console_warn!("island `{name}` is not registered");
+                        continue;
+                    };
+                    let data = element.get_attribute("data-props").unwrap_or_default();
+                    // Every island gets its own scope so that they do not share contexts.
+                    let _ = create_child_scope(|| {
+                        hydrate_in_scope(
+                            || {
+                                provide_context(IslandContext);
+                                island(&data)
+                            },
+                            &element,
+                        );
+                    });
+                }
+            }
+        })
+    }
+}
+
+impl fmt::Debug for Islands {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Islands")
+            .field("islands", &self.islands.keys())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+
+    #[derive(Props, Serialize, Deserialize)]
+    struct CounterProps {
+        initial: i32,
+        label: String,
+    }
+
+    #[sycamore::island]
+    fn Counter(props: CounterProps) -> View {
+        let count = create_signal(props.initial);
+        view! {
+            button { (props.label) ": " (count) }
+        }
+    }
+
+    #[sycamore::island]
+    fn Static() -> View {
+        view! { p { "Inside" } }
+    }
+
+    #[test]
+    fn render_island() {
+        let html = render_to_string(|| {
+            view! {
+                NoHydrate {
+                    h1 { "Title" }
+                    Counter(initial=1, label="Count \"a\"".to_string())
+                    Static {}
+                }
+            }
+        });
+        expect![[r#"<h1>Title</h1><sycamore-island data-island="Counter" data-props="{&quot;initial&quot;:1,&quot;label&quot;:&quot;Count \&quot;a\&quot;&quot;}"><button data-hk="0.0">Count "a": <!--/-->1<!--/--></button></sycamore-island><sycamore-island data-island="Static" data-props="null"><p data-hk="0.0">Inside</p></sycamore-island>"#]]
+            .assert_eq(&html);
+    }
+
+    #[test]
+    fn nested_island_is_rendered_inline() {
+        #[sycamore::island]
+        fn Outer() -> View {
+            view! { div { Static {} } }
+        }
+
+        let html = render_to_string(|| view! { NoHydrate { Outer {} } });
+        expect![[r#"<sycamore-island data-island="Outer" data-props="null"><div data-hk="0.0"><p data-hk="0.1">Inside</p></div></sycamore-island>"#]]
+            .assert_eq(&html);
+    }
+}
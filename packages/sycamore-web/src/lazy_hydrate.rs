@@ -0,0 +1,260 @@
+//! Deferring the hydration of parts of the page.
+
+#[cfg_not_ssr]
+#[cfg(feature = "hydrate")]
+use std::num::NonZeroU32;
+
+use crate::*;
+
+/// When the children of a [`LazyHydrate`] boundary are hydrated.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HydrationStrategy {
+    /// Hydrate once the content enters the viewport.
+    #[default]
+    Visible,
+    /// Hydrate once the browser is idle.
+    Idle,
+    /// Hydrate once the user interacts with the content, e.g. by clicking on it or focusing it.
+    Interaction,
+}
+
+/// The events that trigger hydration with [`HydrationStrategy::Interaction`].
+#[cfg_not_ssr]
+#[cfg(feature = "hydrate")]
+const INTERACTION_EVENTS: [&str; 5] = ["pointerdown", "click", "keydown", "focusin", "input"];
+
+/// Defers hydrating its children until the condition of the `strategy` is met. Until then, the
+/// server rendered HTML is left as is and is not interactive.
+///
+/// In SSR mode, the children are rendered into a `<sycamore-lazy>` element with their own
+/// hydration keys, just like a [`Suspense`](crate::Suspense) boundary, so that they can be
+/// hydrated independently of the rest of the app.
+///
+/// With [`HydrationStrategy::Interaction`], the children are hydrated as soon as the triggering
+/// event reaches the boundary. The event is then delivered to the newly hydrated event handlers,
+/// so the interaction is not lost.
+///
+/// If the boundary is created on the client after hydration is over, the children are rendered
+/// right away.
+///
+/// # Example
+/// ```
+/// # use sycamore::prelude::*;
+/// # use sycamore::web::{HydrationStrategy, LazyHydrate};
+/// # #[component] fn Comments() -> View { view! {} }
+/// # fn App() -> View {
+/// view! {
+///     LazyHydrate(strategy=HydrationStrategy::Visible) {
+///         Comments {}
+///     }
+/// }
+/// # }
+/// ```
+#[component(inline_props)]
+pub fn LazyHydrate(#[prop(default)] strategy: HydrationStrategy, children: Children) -> View {
+    is_ssr! {
+        let _ = strategy;
+        let key = use_suspense_key();
+        let children = HydrationRegistry::in_suspense_scope(key, move || children.call());
+        view! {
+            sycamore-lazy(data-key=key.to_string(), style="display:contents") {
+                (children)
+            }
+        }
+    }
+    is_not_ssr! {
+        if !IS_HYDRATING.get() {
+            let _ = strategy;
+            return children.call();
+        }
+        #[cfg(not(feature = "hydrate"))]
+        unreachable!("cannot hydrate without the `hydrate` feature");
+        #[cfg(feature = "hydrate")]
+        {
+            let view = view! { sycamore-lazy() };
+            let element = view.nodes[0]
+                .as_web_sys()
+                .unchecked_ref::<web_sys::Element>()
+                .clone();
+            let key: NonZeroU32 = element
+                .get_attribute("data-key")
+                .unwrap()
+                .parse()
+                .unwrap();
+
+            let is_alive = Rc::new(Cell::new(true));
+            on_cleanup({
+                let is_alive = Rc::clone(&is_alive);
+                move || is_alive.set(false)
+            });
+            let scope = use_current_scope();
+            let children = Cell::new(Some(children));
+            let hydrate: Rc<dyn Fn()> = Rc::new({
+                let element = element.clone();
+                move || {
+                    if let Some(children) = children.take().filter(|_| is_alive.get()) {
+                        scope.run_in(|| hydrate_boundary(key, &element, children));
+                    }
+                }
+            });
+            match strategy {
+                HydrationStrategy::Visible => hydrate_on_visible(&element, hydrate),
+                HydrationStrategy::Idle => hydrate_on_idle(hydrate),
+                HydrationStrategy::Interaction => hydrate_on_interaction(&element, hydrate),
+            }
+            view
+        }
+    }
+}
+
+/// Hydrates the children of the boundary with the given `key`. This is also used by [`Cache`].
+#[cfg_not_ssr]
+#[cfg(feature = "hydrate")]
+pub(crate) fn hydrate_boundary(key: NonZeroU32, element: &web_sys::Element, children: Children) {
+    // The nodes might have been removed from `HYDRATE_NODES` by another call to `hydrate` in the
+    // meantime, e.g. for an island.
+    let existing_nodes = element.query_selector_all("[data-hk]").unwrap();
+    TEXT_CURSORS.with(|cursors| cursors.borrow_mut().clear());
+    HYDRATE_NODES.with(|nodes| {
+        let mut nodes = nodes.borrow_mut();
+        for i in 0..existing_nodes.length() {
+            let node = existing_nodes.get(i).unwrap();
+            let hk = node
+                .unchecked_ref::<web_sys::Element>()
+                .get_attribute("data-hk")
+                .unwrap();
+            let key = HydrationKey::parse(&hk).expect("could not parse hydration key");
+            nodes.insert(key, HydrateNode::from_web_sys(node));
+        }
+    });
+
+    let is_hydrating = IS_HYDRATING.replace(true);
+    let view = HydrationRegistry::in_suspense_scope(key, move || children.call());
+    let mut parent = HydrateNode::from_web_sys(element.clone().into());
+    for node in view.nodes {
+        parent.append_child(node);
+    }
+    IS_HYDRATING.set(is_hydrating);
+}
+
+#[cfg_not_ssr]
+#[cfg(feature = "hydrate")]
+fn hydrate_on_visible(element: &web_sys::Element, hydrate: Rc<dyn Fn()>) {
+    // The `<sycamore-lazy>` element has `display: contents` so it never intersects with the
+    // viewport itself. Observe its children instead.
+    let mut children = Vec::new();
+    let mut next = element.first_element_child();
+    while let Some(current) = next {
+        next = current.next_element_sibling();
+        children.push(current);
+    }
+    if children.is_empty() {
+        hydrate();
+        return;
+    }
+
+    let callback = Closure::<dyn FnMut(js_sys::Array, web_sys::IntersectionObserver)>::new(
+        move |entries: js_sys::Array, observer: web_sys::IntersectionObserver| {
+            let is_visible = entries.iter().any(|entry| {
+                entry
+                    .unchecked_into::<web_sys::IntersectionObserverEntry>()
+                    .is_intersecting()
+            });
+            if is_visible {
+                observer.disconnect();
+                hydrate();
+            }
+        },
+    );
+    let observer = web_sys::IntersectionObserver::new(callback.as_ref().unchecked_ref()).unwrap();
+    for child in &children {
+        observer.observe(child);
+    }
+    on_cleanup(move || {
+        observer.disconnect();
+        drop(callback);
+    });
+}
+
+#[cfg_not_ssr]
+#[cfg(feature = "hydrate")]
+fn hydrate_on_idle(hydrate: Rc<dyn Fn()>) {
+    #[wasm_bindgen]
+    extern "C" {
+        #[wasm_bindgen(js_name = "requestIdleCallback")]
+        fn request_idle_callback(f: &JsValue);
+    }
+
+    let callback = Closure::once_into_js(move || hydrate());
+    // Safari does not support `requestIdleCallback`.
+    if js_sys::Reflect::has(&window(), &"requestIdleCallback".into()).unwrap_or_default() {
+        request_idle_callback(&callback);
+    } else {
+        window()
+            .set_timeout_with_callback(callback.unchecked_ref())
+            .unwrap();
+    }
+}
+
+#[cfg_not_ssr]
+#[cfg(feature = "hydrate")]
+fn hydrate_on_interaction(element: &web_sys::Element, hydrate: Rc<dyn Fn()>) {
+    // The listeners are added in the capture phase so that the boundary is hydrated before the
+    // event reaches its target. The event then continues to the hydrated event handlers.
+    let function = Rc::new(std::cell::OnceCell::<js_sys::Function>::new());
+    let callback = Closure::<dyn FnMut(web_sys::Event)>::new({
+        let element = element.clone();
+        let function = Rc::clone(&function);
+        move |_: web_sys::Event| {
+            if let Some(function) = function.get() {
+                remove_listeners(&element, function);
+            }
+            hydrate();
+        }
+    });
+    let f: &js_sys::Function = callback.as_ref().unchecked_ref();
+    for event in INTERACTION_EVENTS {
+        element
+            .add_event_listener_with_callback_and_bool(event, f, true)
+            .unwrap();
+    }
+    let _ = function.set(f.clone());
+
+    let element = element.clone();
+    on_cleanup(move || {
+        remove_listeners(&element, callback.as_ref().unchecked_ref());
+        drop(callback);
+    });
+
+    fn remove_listeners(element: &web_sys::Element, f: &js_sys::Function) {
+        for event in INTERACTION_EVENTS {
+            element
+                .remove_event_listener_with_callback_and_bool(event, f, true)
+                .unwrap();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use super::*;
+
+    #[test]
+    fn lazy_hydrate_scopes_keys() {
+        let html = render_to_string(|| {
+            view! {
+                p { "Before" }
+                LazyHydrate(strategy=HydrationStrategy::Interaction) {
+                    button { "Lazy" }
+                    LazyHydrate {
+                        span { "Nested" }
+                    }
+                }
+                p { "After" }
+            }
+        });
+        expect![[r#"<p data-hk="0.0">Before</p><sycamore-lazy data-key="1" style="display:contents" data-hk="0.1"><button data-hk="1.0">Lazy</button><sycamore-lazy data-key="2" style="display:contents" data-hk="1.1"><span data-hk="2.0">Nested</span></sycamore-lazy></sycamore-lazy><p data-hk="0.2">After</p>"#]].assert_eq(&html);
+    }
+}
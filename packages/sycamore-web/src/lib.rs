@@ -8,6 +8,12 @@
 //! - `hydrate` - Enables hydration support in DOM node. By default, hydration is disabled to reduce
 //!   binary size.
 //!
+//! - `islands` - Enables islands, i.e. components that are hydrated on their own while the rest
+//!   of the page stays static. Also enables `hydrate`.
+//!
+//! - `server-state` - Enables passing state from the server to the client with
+//!   `provide_server_state` and `use_server_state`.
+//!
 //! - `suspense` - Enables suspense and resources support.
 //!
 //! - `wasm-bindgen-interning` (_default_) - Enables interning for `wasm-bindgen` strings. This
@@ -39,32 +45,51 @@ pub mod events;
 pub mod utils;
 
 mod attributes;
+mod cache;
 mod components;
 mod elements;
+mod error_boundary;
+mod head;
+#[cfg(feature = "islands")]
+mod islands;
 mod iter;
+mod lazy_hydrate;
 mod macros;
 mod node;
 mod noderef;
 mod portal;
 #[cfg(feature = "suspense")]
 mod resource;
+mod server_state;
 mod stable_counter;
 #[cfg(feature = "suspense")]
+mod stream_resource;
+#[cfg(feature = "suspense")]
 mod suspense;
 
 pub(crate) mod view;
 
 pub use self::attributes::*;
+pub use self::cache::*;
 pub use self::components::*;
 pub use self::elements::*;
+pub use self::error_boundary::*;
+pub use self::head::*;
+#[cfg(feature = "islands")]
+pub use self::islands::*;
 pub use self::iter::*;
+pub use self::lazy_hydrate::*;
 pub use self::node::*;
 pub use self::noderef::*;
 pub use self::portal::*;
 #[cfg(feature = "suspense")]
 pub use self::resource::*;
+#[cfg(feature = "server-state")]
+pub use self::server_state::*;
 pub use self::stable_counter::*;
 #[cfg(feature = "suspense")]
+pub use self::stream_resource::*;
+#[cfg(feature = "suspense")]
 pub use self::suspense::*;
 pub use self::view::*;
 
@@ -81,9 +106,11 @@ pub mod rt {
     #[allow(unused_imports)] // Needed for macro support.
     pub use web_sys;
 
-    #[cfg(feature = "suspense")]
-    pub use crate::WrapAsync;
+    #[cfg(feature = "islands")]
+    pub use crate::create_island;
     pub use crate::{bind, custom_element, tags, View};
+    #[cfg(feature = "suspense")]
+    pub use crate::{wrap_async_named, WrapAsync};
 }
 
 /// Re-export of `js-sys` and `wasm-bindgen` for convenience.
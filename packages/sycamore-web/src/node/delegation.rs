@@ -0,0 +1,159 @@
+//! Event delegation. See [`events::set_delegation`].
+
+use std::cell::RefCell;
+use std::collections::HashSet;
+
+use super::*;
+
+thread_local! {
+    static NEXT_ROOT_ID: Cell<usize> = const { Cell::new(0) };
+}
+
+/// A node that handles delegated events for all the elements that are rendered under it.
+///
+/// This is provided as a context by [`render_in_scope`] and [`Portal`] when event delegation is
+/// enabled.
+#[derive(Clone)]
+pub(crate) struct DelegationRoot {
+    /// Used to make sure that handlers are only dispatched by the root they were registered with,
+    /// even if roots are nested.
+    id: usize,
+    node: web_sys::Node,
+    /// The scope of the root. Listeners on the root are removed when this scope is disposed.
+    scope: NodeHandle,
+    /// The names of the events that already have a listener on the root.
+    listeners: Rc<RefCell<HashSet<String>>>,
+}
+
+impl DelegationRoot {
+    /// Makes `node` the root for delegated events of all elements created in the current scope, if
+    /// event delegation is enabled.
+    pub fn provide(node: &web_sys::Node) {
+        if events::is_delegation_enabled() {
+            let id = NEXT_ROOT_ID.with(|next| next.replace(next.get() + 1));
+            provide_context(Self {
+                id,
+                node: node.clone(),
+                scope: use_current_scope(),
+                listeners: Default::default(),
+            });
+        }
+    }
+
+    /// Returns the delegation root of the current scope if `name` can be delegated with
+    /// `options`.
+    pub fn find(name: &str, options: events::EventOptions) -> Option<Self> {
+        if options.has_listener_options() || !events::DELEGATED_EVENTS.contains(&name) {
+            return None;
+        }
+        try_use_context::<Self>()
+    }
+
+    /// The name of the JS property on each node that holds the delegated handlers for `name`.
+    fn key(&self, name: &str) -> JsValue {
+        format!("$$sycamore{}_{name}", self.id).into()
+    }
+
+    /// Stores `handler` on `node` so that it is called when the root receives the event. The
+    /// handler is removed again when the current scope is disposed.
+    pub fn add_handler(
+        &self,
+        node: &web_sys::Node,
+        name: &str,
+        handler: impl FnMut(web_sys::Event) + 'static,
+    ) {
+        self.ensure_listener(name);
+
+        let key = self.key(name);
+        let handlers = js_sys::Reflect::get(node, &key).unwrap_throw();
+        let handlers: js_sys::Set = if handlers.is_undefined() {
+            let handlers = js_sys::Set::new(&JsValue::UNDEFINED);
+            js_sys::Reflect::set(node, &key, &handlers).unwrap_throw();
+            handlers
+        } else {
+            handlers.unchecked_into()
+        };
+
+        let cb = Closure::wrap(Box::new(handler) as Box<dyn FnMut(_)>);
+        let f: JsValue = cb.as_ref().clone();
+        handlers.add(&f);
+        on_cleanup(move || {
+            handlers.delete(&f);
+            drop(cb);
+        });
+    }
+
+    /// Adds a listener for `name` on the root node if there is none yet.
+    fn ensure_listener(&self, name: &str) {
+        if !self.listeners.borrow_mut().insert(name.to_string()) {
+            return;
+        }
+
+        let root = self.node.clone();
+        let key = self.key(name);
+        let cb = Closure::wrap(
+            Box::new(move |ev: web_sys::Event| dispatch(&root, &key, &ev)) as Box<dyn FnMut(_)>,
+        );
+        self.node
+            .add_event_listener_with_callback(name, cb.as_ref().unchecked_ref())
+            .unwrap_throw();
+
+        let node = self.node.clone();
+        let name = name.to_string();
+        self.scope.run_in(move || {
+            on_cleanup(move || {
+                node.remove_event_listener_with_callback(&name, cb.as_ref().unchecked_ref())
+                    .unwrap_throw();
+                drop(cb);
+            })
+        });
+    }
+}
+
+/// Calls the handlers stored under `key` on every node from the target of `ev` up to (but not
+/// including) `root`, like the browser would if the handlers were attached directly.
+fn dispatch(root: &web_sys::Node, key: &JsValue, ev: &web_sys::Event) {
+    let current_target: JsValue = "currentTarget".into();
+    // Collect the path before calling any handler. Like in the browser, a handler that removes
+    // its node from the DOM should not stop the event from bubbling up to the old ancestors.
+    let mut path = Vec::new();
+    let mut node = ev.target().map(JsCast::unchecked_into::<web_sys::Node>);
+    while let Some(current) = node {
+        if &current == root {
+            break;
+        }
+        node = current.parent_node();
+        path.push(current);
+    }
+
+    for current in path {
+        let handlers = js_sys::Reflect::get(&current, key).unwrap_throw();
+        if !handlers.is_undefined() {
+            // Make `currentTarget` point to the node that the handlers were attached to.
+            let descriptor = js_sys::Object::new();
+            js_sys::Reflect::set(&descriptor, &"configurable".into(), &JsValue::TRUE)
+                .unwrap_throw();
+            js_sys::Reflect::set(&descriptor, &"value".into(), &current).unwrap_throw();
+            js_sys::Reflect::define_property(
+                ev.unchecked_ref::<js_sys::Object>(),
+                &current_target,
+                &descriptor,
+            )
+            .unwrap_throw();
+
+            // Copy the handlers first since a handler might remove itself, e.g. by removing the
+            // node it is attached to.
+            for handler in js_sys::Array::from(&handlers).iter() {
+                let _ = handler
+                    .unchecked_ref::<js_sys::Function>()
+                    .call1(&JsValue::UNDEFINED, ev);
+            }
+            if ev.cancel_bubble() {
+                break;
+            }
+        }
+    }
+    // Restore the native `currentTarget` getter.
+    js_sys::Reflect::delete_property(ev.unchecked_ref::<js_sys::Object>(), &current_target)
+        .unwrap_throw();
+}
@@ -154,16 +154,21 @@ impl ViewHtmlNode for DomNode {
         }
     }
 
-    fn set_property(&mut self, name: Cow<'static, str>, value: MaybeDyn<JsValue>) {
+    fn set_property(&mut self, name: Cow<'static, str>, value: MaybeDyn<PropertyValue>) {
         if let Some(value) = value.as_static() {
-            assert!(js_sys::Reflect::set(&self.raw, &name.as_ref().into(), value).unwrap_throw())
+            assert!(
+                js_sys::Reflect::set(&self.raw, &name.as_ref().into(), &value.clone().into())
+                    .unwrap_throw()
+            )
         } else {
             let node = self.raw.clone().unchecked_into::<web_sys::Element>();
             create_effect(move || {
-                assert!(
-                    js_sys::Reflect::set(&node, &name.as_ref().into(), &value.get_clone())
-                        .unwrap_throw()
+                assert!(js_sys::Reflect::set(
+                    &node,
+                    &name.as_ref().into(),
+                    &value.get_clone().into()
                 )
+                .unwrap_throw())
             });
         }
     }
@@ -171,12 +176,32 @@ impl ViewHtmlNode for DomNode {
     fn set_event_handler(
         &mut self,
         name: Cow<'static, str>,
+        options: events::EventOptions,
         handler: impl FnMut(web_sys::Event) + 'static,
     ) {
+        let handler = options.wrap_handler(handler);
+        if let Some(root) = DelegationRoot::find(&name, options) {
+            root.add_handler(&self.raw, &name, handler);
+            return;
+        }
         let cb = Closure::wrap(Box::new(handler) as Box<dyn FnMut(_)>);
-        self.raw
-            .add_event_listener_with_callback(&name, cb.as_ref().unchecked_ref())
-            .unwrap();
+        if options.has_listener_options() {
+            let listener_options = web_sys::AddEventListenerOptions::new();
+            listener_options.set_once(options.once);
+            listener_options.set_capture(options.capture);
+            listener_options.set_passive(options.passive);
+            self.raw
+                .add_event_listener_with_callback_and_add_event_listener_options(
+                    &name,
+                    cb.as_ref().unchecked_ref(),
+                    &listener_options,
+                )
+                .unwrap();
+        } else {
+            self.raw
+                .add_event_listener_with_callback(&name, cb.as_ref().unchecked_ref())
+                .unwrap();
+        }
         on_cleanup(|| drop(cb));
     }
 
@@ -30,6 +30,9 @@ pub fn render_in_scope(view: impl FnOnce() -> View, parent: &web_sys::Node) {
         panic!("`render_in_scope` is not available in SSR mode");
     } else {
         IS_HYDRATING.set(false);
+        is_not_ssr! {
+            DelegationRoot::provide(parent);
+        }
         let nodes = view().nodes;
         for node in nodes {
             parent.append_child(node.as_web_sys()).unwrap();
@@ -94,6 +97,10 @@ pub fn hydrate_in_scope(view: impl FnOnce() -> View, parent: &web_sys::Node) {
 
         HYDRATE_NODES.with(|nodes| {
             let mut nodes = nodes.borrow_mut();
+            // Nodes that were not hydrated by a previous call, e.g. for another island, should not
+            // be picked up by this one.
+            nodes.clear();
+            TEXT_CURSORS.with(|cursors| cursors.borrow_mut().clear());
             let len = existing_nodes.length();
             for i in 0..len {
                 let node = existing_nodes.get(i).unwrap();
@@ -107,6 +114,7 @@ pub fn hydrate_in_scope(view: impl FnOnce() -> View, parent: &web_sys::Node) {
         IS_HYDRATING.set(true);
         provide_context(mode);
         provide_context(HydrationRegistry::new());
+        DelegationRoot::provide(parent);
         let nodes = view().nodes;
         // We need to append `nodes` to the `parent` so that the top level nodes also get properly
         // hydrated.
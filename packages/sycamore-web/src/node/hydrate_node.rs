@@ -13,11 +13,21 @@ enum NodeState {
     Hydrated(DomNode),
     /// A text node that is dynamic. Replace the original text node with this one when hydrating.
     TextDynamic(DomNode),
-    /// A static text node. No need to be hydrated.
-    TextStatic,
+    /// A static text node. No need to be hydrated. The text is kept to check for mismatches.
+    TextStatic(Cow<'static, str>),
     /// A marker node that has not been hydrated yet. The node will be hydrated when it is
     /// appended.
     Marker(DomNode),
+    /// An element that was rendered on the client in place of a server rendered element with a
+    /// different tag because of a [`HydrationMismatch`]. Its children are still hydrated and are
+    /// moved over from the server rendered element when they are appended.
+    Recovered(DomNode),
+    /// An element that is missing from the server HTML. It is rendered on the client instead.
+    ///
+    /// The mismatch is only reported once the element is appended to a node that is being
+    /// hydrated. Its children are missing as well, so they are rendered on the client without
+    /// reporting a mismatch for each of them.
+    Missing(DomNode, Box<HydrationMismatch>),
 }
 
 impl NodeState {
@@ -25,7 +35,11 @@ impl NodeState {
     #[cfg_attr(debug_assertions, track_caller)]
     fn unwrap(self) -> DomNode {
         match self {
-            Self::Hydrated(node) | Self::TextDynamic(node) | Self::Marker(node) => node,
+            Self::Hydrated(node)
+            | Self::TextDynamic(node)
+            | Self::Marker(node)
+            | Self::Recovered(node)
+            | Self::Missing(node, _) => node,
             _ => panic!("node is not hydrated"),
         }
     }
@@ -34,7 +48,11 @@ impl NodeState {
     #[cfg_attr(debug_assertions, track_caller)]
     fn unwrap_ref(&self) -> &DomNode {
         match self {
-            Self::Hydrated(node) | Self::TextDynamic(node) | Self::Marker(node) => node,
+            Self::Hydrated(node)
+            | Self::TextDynamic(node)
+            | Self::Marker(node)
+            | Self::Recovered(node)
+            | Self::Missing(node, _) => node,
             _ => panic!("node is not hydrated"),
         }
     }
@@ -43,7 +61,11 @@ impl NodeState {
     #[cfg_attr(debug_assertions, track_caller)]
     fn unwrap_mut(&mut self) -> &mut DomNode {
         match self {
-            Self::Hydrated(node) | Self::TextDynamic(node) | Self::Marker(node) => node,
+            Self::Hydrated(node)
+            | Self::TextDynamic(node)
+            | Self::Marker(node)
+            | Self::Recovered(node)
+            | Self::Missing(node, _) => node,
             _ => panic!("node is not hydrated"),
         }
     }
@@ -57,13 +79,12 @@ impl From<HydrateNode> for View<HydrateNode> {
 
 impl ViewNode for HydrateNode {
     fn append_child(&mut self, child: Self) {
-        if IS_HYDRATING.get() {
+        if self.is_hydrating() {
             match child.0 {
                 NodeState::Hydrated(_) => {
                     // Noop for hydration since node is already in right place.
                 }
                 NodeState::TextDynamic(node) => {
-                    console_warn!("text dynamic");
                     // Search self for an empty comment node. Once found, the next node should be
                     // the text node. Hydrate the text node and remove the comment node.
                     let mut next = self.as_web_sys().first_child();
@@ -76,15 +97,33 @@ impl ViewNode for HydrateNode {
                                     .replace_child(&node.as_web_sys(), &text_node)
                                     .unwrap();
                                 self.as_web_sys().remove_child(&comment).unwrap();
+                                // Static text that comes after this is in the next text node.
+                                set_text_cursor(
+                                    self.as_web_sys(),
+                                    node.as_web_sys().next_sibling(),
+                                );
                                 return;
                             }
                         }
                         next = current.next_sibling();
                     }
-                    panic!("text node not found during hydration");
+                    self.recover_missing(
+                        node,
+                        "dynamic text",
+                        "text node not found during hydration",
+                    );
                 }
-                NodeState::TextStatic => {
-                    // Noop for hydration.
+                NodeState::TextStatic(text) => {
+                    // Noop for hydration. Only check that the text is actually there.
+                    if cfg!(debug_assertions) {
+                        if let Err(found) = check_static_text(self.as_web_sys(), &text) {
+                            report_hydration_mismatch(HydrationMismatch::new(
+                                self.key(),
+                                format!("text {text:?}"),
+                                found,
+                            ));
+                        }
+                    }
                 }
                 NodeState::Marker(node) => {
                     // Search self for a comment node with content '/'. Once found, this is the
@@ -104,11 +143,25 @@ impl ViewNode for HydrateNode {
                         }
                         next = current.next_sibling();
                     }
-                    panic!("hydration marker node not found");
+                    self.recover_missing(node, "a marker", "hydration marker node not found");
+                }
+                NodeState::Recovered(_) => {
+                    // Noop since the element already replaced the server rendered element.
+                }
+                NodeState::Missing(node, mismatch) => {
+                    let key = mismatch.key.unwrap();
+                    if !report_hydration_mismatch(*mismatch) {
+                        panic!("node with hk `{key}` not found");
+                    }
+                    self.0.unwrap_mut().append_child(node);
                 }
             }
         } else {
-            self.0.unwrap_mut().append_child(child.0.unwrap());
+            let child = match child.0 {
+                NodeState::TextStatic(text) => DomNode::create_text_node(text),
+                state => state.unwrap(),
+            };
+            self.0.unwrap_mut().append_child(child);
         }
     }
 
@@ -119,20 +172,156 @@ impl ViewNode for HydrateNode {
     }
 }
 
-fn check_node(node: &web_sys::Node, tag: &str, key: HydrationKey) {
+impl HydrateNode {
+    /// Whether this node should be hydrated, i.e. whether we are hydrating and the node was
+    /// rendered on the server.
+    fn is_hydrating(&self) -> bool {
+        IS_HYDRATING.get() && !matches!(self.0, NodeState::Recovered(_) | NodeState::Missing(..))
+    }
+
+    /// The hydration key of this element, if it has one.
+    fn key(&self) -> Option<HydrationKey> {
+        self.as_web_sys()
+            .dyn_ref::<web_sys::Element>()?
+            .get_attribute("data-hk")
+            .and_then(|key| HydrationKey::parse(&key))
+    }
+
+    /// Reports that `node` could not be found in this element. If we should recover, `node` is
+    /// appended to this element. Otherwise, we panic with `msg`.
+    #[cfg_attr(debug_assertions, track_caller)]
+    fn recover_missing(&mut self, node: DomNode, expected: &str, msg: &str) {
+        if report_hydration_mismatch(HydrationMismatch::new(
+            self.key(),
+            expected.to_string(),
+            "nothing".to_string(),
+        )) {
+            self.0.unwrap_mut().append_child(node);
+        } else {
+            panic!("{msg}");
+        }
+    }
+}
+
+impl HydrationMismatch {
+    /// Creates a new mismatch in the current component.
+    fn new(key: Option<HydrationKey>, expected: String, found: String) -> Self {
+        Self {
+            key,
+            expected,
+            found,
+            component_path: sycamore_core::component_path(),
+        }
+    }
+}
+
+/// Reports a [`HydrationMismatch`] to the handler and returns whether we should recover from it.
+fn report_hydration_mismatch(mismatch: HydrationMismatch) -> bool {
+    match HYDRATION_MISMATCH_HANDLER.with(|handler| handler.borrow().clone()) {
+        Some(handler) => handler(&mismatch),
+        None => console_error!("{mismatch}"),
+    }
+    HYDRATION_MISMATCH_RECOVERY.with(Cell::get)
+}
+
+/// Where the next static text is expected inside of a parent that is being hydrated.
+pub(crate) struct TextCursor {
+    parent: web_sys::Node,
+    /// The node to continue searching from.
+    node: Option<web_sys::Node>,
+    /// How much of `node` was already matched, if it is a text node.
+    offset: usize,
+}
+
+/// Moves the text cursor of `parent` to `node`.
+fn set_text_cursor(parent: &web_sys::Node, node: Option<web_sys::Node>) {
+    TEXT_CURSORS.with(|cursors| {
+        let mut cursors = cursors.borrow_mut();
+        match cursors.iter_mut().find(|cursor| cursor.parent == *parent) {
+            Some(cursor) => {
+                cursor.node = node;
+                cursor.offset = 0;
+            }
+            None => cursors.push(TextCursor {
+                parent: parent.clone(),
+                node,
+                offset: 0,
+            }),
+        }
+    });
+}
+
+/// Checks that `text` comes next in the text nodes directly inside `parent`, after the static text
+/// that was checked before. Adjacent static text nodes are merged into a single text node by the
+/// browser, so each one is matched exactly against the next part of a text node.
+///
+/// Returns what was found instead if the text does not match.
+fn check_static_text(parent: &web_sys::Node, text: &str) -> Result<(), String> {
+    if text.is_empty() {
+        return Ok(());
+    }
+    TEXT_CURSORS.with(|cursors| {
+        let mut cursors = cursors.borrow_mut();
+        let cursor = match cursors.iter().position(|cursor| cursor.parent == *parent) {
+            Some(i) => &mut cursors[i],
+            None => {
+                cursors.push(TextCursor {
+                    parent: parent.clone(),
+                    node: parent.first_child(),
+                    offset: 0,
+                });
+                cursors.last_mut().unwrap()
+            }
+        };
+        while let Some(current) = cursor.node.clone() {
+            if current.node_type() == web_sys::Node::TEXT_NODE {
+                let content = current.text_content().unwrap_or_default();
+                let rest = content.get(cursor.offset..).unwrap_or_default();
+                if !rest.starts_with(text) {
+                    return Err(format!("text {rest:?}"));
+                }
+                cursor.offset += text.len();
+                if cursor.offset == content.len() {
+                    cursor.node = current.next_sibling();
+                    cursor.offset = 0;
+                }
+                return Ok(());
+            }
+            cursor.node = current.next_sibling();
+            cursor.offset = 0;
+        }
+        Err("nothing".to_string())
+    })
+}
+
+/// Takes the server rendered element with the given key out of [`HYDRATE_NODES`] and checks that
+/// it matches what the client rendered. If it does not and we should recover from the mismatch,
+/// a new element is created with `create` instead.
+fn hydrate_element(key: HydrationKey, tag: &str, create: impl FnOnce() -> DomNode) -> HydrateNode {
+    let Some(node) = HYDRATE_NODES.with(|nodes| nodes.borrow_mut().remove(&key)) else {
+        let mismatch = HydrationMismatch::new(Some(key), format!("<{tag}>"), "nothing".to_string());
+        return HydrateNode(NodeState::Missing(create(), Box::new(mismatch)));
+    };
     if cfg!(debug_assertions) {
-        let node = node.unchecked_ref::<web_sys::Element>();
-        node.set_attribute("data-hydrated", "").unwrap();
-
-        assert_eq!(node.node_type(), web_sys::Node::ELEMENT_NODE);
-        assert_eq!(node.get_attribute("data-hk"), Some(key.to_string()));
-        let actual_tag = node.tag_name().to_lowercase();
-        if actual_tag != tag {
-            console_warn!(
-                "hydration tag name mismatch at `{key}`: expected '{tag}', got '{actual_tag}'"
-            );
+        let element = node.as_web_sys().unchecked_ref::<web_sys::Element>();
+        element.set_attribute("data-hydrated", "").unwrap();
+
+        let found = element.tag_name();
+        if !found.eq_ignore_ascii_case(tag)
+            && report_hydration_mismatch(HydrationMismatch::new(
+                Some(key),
+                format!("<{tag}>"),
+                format!("<{}>", found.to_lowercase()),
+            ))
+        {
+            // Render the element on the client in place of the server rendered element. The
+            // children are moved over when they are appended.
+            let new = create();
+            element.replace_with_with_node_1(new.as_web_sys()).unwrap();
+            return HydrateNode(NodeState::Recovered(new));
         }
     }
+    node
 }
 
 impl ViewHtmlNode for HydrateNode {
@@ -140,11 +329,7 @@ impl ViewHtmlNode for HydrateNode {
         if IS_HYDRATING.get() {
             let reg: HydrationRegistry = use_context();
             let key = reg.next_key();
-            let node = HYDRATE_NODES
-                .with(|nodes| nodes.borrow_mut().remove(&key))
-                .unwrap_or_else(|| panic!("node with hk `{key}` not found"));
-            check_node(node.as_web_sys(), &tag, key);
-            node
+            hydrate_element(key, &tag, || DomNode::create_element(tag.clone()))
         } else {
             Self(NodeState::Hydrated(DomNode::create_element(tag)))
         }
@@ -154,16 +339,9 @@ impl ViewHtmlNode for HydrateNode {
         if IS_HYDRATING.get() {
             let reg: HydrationRegistry = use_context();
             let key = reg.next_key();
-            let node = HYDRATE_NODES
-                .with(|nodes| nodes.borrow_mut().remove(&key))
-                .unwrap_or_else(|| panic!("node with hk `{key}` not found"));
-            if cfg!(debug_assertions) {
-                node.as_web_sys()
-                    .unchecked_ref::<web_sys::Element>()
-                    .set_attribute("data-hydrated", "")
-                    .unwrap();
-            }
-            node
+            hydrate_element(key, &tag, || {
+                DomNode::create_element_ns(namespace, tag.clone())
+            })
         } else {
             Self(NodeState::Hydrated(DomNode::create_element_ns(
                 namespace, tag,
@@ -173,7 +351,7 @@ impl ViewHtmlNode for HydrateNode {
 
     fn create_text_node(text: Cow<'static, str>) -> Self {
         if IS_HYDRATING.get() {
-            Self(NodeState::TextStatic)
+            Self(NodeState::TextStatic(text))
         } else {
             Self(NodeState::Hydrated(DomNode::create_text_node(text)))
         }
@@ -194,7 +372,7 @@ impl ViewHtmlNode for HydrateNode {
 
     fn set_attribute(&mut self, name: Cow<'static, str>, value: StringAttribute) {
         // FIXME: use setAttributeNS if SVG
-        if IS_HYDRATING.get() {
+        if self.is_hydrating() {
             // Noop if value is static since attributes are already set.
             if value.as_static().is_none() {
                 let node = self
@@ -219,7 +397,7 @@ impl ViewHtmlNode for HydrateNode {
 
     fn set_bool_attribute(&mut self, name: Cow<'static, str>, value: BoolAttribute) {
         // FIXME: use setAttributeNS if SVG
-        if IS_HYDRATING.get() {
+        if self.is_hydrating() {
             if value.as_static().is_none() {
                 let node = self
                     .as_web_sys()
@@ -244,21 +422,24 @@ impl ViewHtmlNode for HydrateNode {
         }
     }
 
-    fn set_property(&mut self, name: Cow<'static, str>, value: MaybeDyn<JsValue>) {
+    fn set_property(&mut self, name: Cow<'static, str>, value: MaybeDyn<PropertyValue>) {
         self.0.unwrap_mut().set_property(name, value);
     }
 
     fn set_event_handler(
         &mut self,
         name: Cow<'static, str>,
+        options: events::EventOptions,
         handler: impl FnMut(web_sys::Event) + 'static,
     ) {
-        self.0.unwrap_mut().set_event_handler(name, handler);
+        self.0
+            .unwrap_mut()
+            .set_event_handler(name, options, handler);
     }
 
     fn set_inner_html(&mut self, inner_html: Cow<'static, str>) {
         // If we are hydrating, inner HTML should already be set.
-        if !IS_HYDRATING.get() {
+        if !self.is_hydrating() {
             self.0.unwrap_mut().set_inner_html(inner_html);
         }
     }
@@ -277,4 +458,7 @@ thread_local! {
     /// A list of nodes to be hydrated. The `Vec` should be sorted in reverse order of hydration
     /// key. Every time a node is hydrated, it should be popped from this list.
     pub(crate) static HYDRATE_NODES: LazyCell<RefCell<HashMap<HydrationKey, HydrateNode>>> = LazyCell::new(Default::default);
+    /// The text cursors of the parents that are being hydrated. Cleared whenever
+    /// [`HYDRATE_NODES`] is filled.
+    pub(crate) static TEXT_CURSORS: RefCell<Vec<TextCursor>> = const { RefCell::new(Vec::new()) };
 }
@@ -15,24 +15,35 @@ cfg_not_ssr_item!(
 cfg_ssr_item!(
     mod ssr_node;
 );
+cfg_not_ssr_item!(
+    mod delegation;
+);
 mod dom_render;
+mod ssr_document;
 mod ssr_render;
+mod ssr_request;
 
 // We add this so that we get IDE support in Rust Analyzer.
 #[cfg(rust_analyzer)]
+mod delegation;
+#[cfg(rust_analyzer)]
 mod dom_node;
 #[cfg(rust_analyzer)]
 mod hydrate_node;
 
+#[cfg_not_ssr]
+pub(crate) use delegation::*;
 #[cfg_not_ssr]
 pub use dom_node::*;
 pub use dom_render::*;
 #[cfg_not_ssr]
 #[cfg(feature = "hydrate")]
 pub use hydrate_node::*;
+pub use ssr_document::*;
 #[cfg_ssr]
 pub use ssr_node::*;
 pub use ssr_render::*;
+pub use ssr_request::*;
 
 /// A trait that should be implemented for anything that represents an HTML node.
 pub trait ViewHtmlNode: ViewNode {
@@ -54,11 +65,16 @@ pub trait ViewHtmlNode: ViewNode {
     /// Set a boolean HTML attribute.
     fn set_bool_attribute(&mut self, name: Cow<'static, str>, value: BoolAttribute);
     /// Set a JS property on an element.
-    fn set_property(&mut self, name: Cow<'static, str>, value: MaybeDyn<JsValue>);
-    /// Set an event handler on an element.
+    ///
+    /// The value is a [`PropertyValue`] rather than a `JsValue` so that SSR backends can render
+    /// well-known properties as attributes.
+    fn set_property(&mut self, name: Cow<'static, str>, value: MaybeDyn<PropertyValue>);
+    /// Set an event handler on an element. `options` control how the listener is attached and
+    /// whether the event should be filtered or modified before `handler` is called.
     fn set_event_handler(
         &mut self,
         name: Cow<'static, str>,
+        options: events::EventOptions,
         handler: impl FnMut(web_sys::Event) + 'static,
     );
     /// Set the inner HTML value of an element.
@@ -137,6 +153,38 @@ impl Default for HydrationRegistry {
     }
 }
 
+/// Global counter for providing suspense keys. These are also used by [`LazyHydrate`] boundaries.
+#[derive(Debug, Clone, Copy)]
+struct SuspenseCounter {
+    next: Signal<NonZeroU32>,
+}
+
+impl SuspenseCounter {
+    fn new() -> Self {
+        Self {
+            next: create_signal(NonZeroU32::new(1).unwrap()),
+        }
+    }
+}
+
+/// Get the next suspense key.
+pub fn use_suspense_key() -> NonZeroU32 {
+    let global_scope = use_global_scope();
+    let counter = global_scope.run_in(|| use_context_or_else(SuspenseCounter::new));
+
+    let next = counter.next.get();
+    counter.next.set(next.checked_add(1).unwrap());
+    next
+}
+
+/// Get the suspense key that the next call to [`use_suspense_key`] will return.
+#[cfg_ssr]
+pub(crate) fn peek_suspense_key() -> NonZeroU32 {
+    let global_scope = use_global_scope();
+    let counter = global_scope.run_in(|| use_context_or_else(SuspenseCounter::new));
+    counter.next.get()
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct HydrationKey {
     /// Suspense key, or 0 if not in a suspense boundary.
@@ -160,6 +208,61 @@ impl HydrationKey {
     }
 }
 
+/// A difference between the HTML that was rendered on the server and the view that is rendered
+/// on the client, found while hydrating.
+///
+/// Tag names and static text are only checked in debug builds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HydrationMismatch {
+    /// The hydration key of the mismatching element, or of the parent element for text.
+    pub key: Option<HydrationKey>,
+    /// What the client rendered.
+    pub expected: String,
+    /// What was found in the HTML from the server.
+    pub found: String,
+    /// The components in which the mismatch was found, from outermost to innermost. This is only
+    /// available in debug builds.
+    pub component_path: Vec<&'static str>,
+}
+
+impl fmt::Display for HydrationMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "hydration mismatch")?;
+        if let Some(key) = self.key {
+            write!(f, " at `{key}`")?;
+        }
+        if !self.component_path.is_empty() {
+            write!(f, " in `{}`", self.component_path.join(" > "))?;
+        }
+        write!(f, ": expected {}, found {}", self.expected, self.found)
+    }
+}
+
+type HydrationMismatchHandler = Rc<dyn Fn(&HydrationMismatch)>;
+
+thread_local! {
+    static HYDRATION_MISMATCH_HANDLER: std::cell::RefCell<Option<HydrationMismatchHandler>> =
+        const { std::cell::RefCell::new(None) };
+    static HYDRATION_MISMATCH_RECOVERY: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Sets the function that is called with every [`HydrationMismatch`]. By default, mismatches are
+/// logged to the console as errors.
+pub fn set_hydration_mismatch_handler(f: impl Fn(&HydrationMismatch) + 'static) {
+    HYDRATION_MISMATCH_HANDLER.with(|handler| *handler.borrow_mut() = Some(Rc::new(f)));
+}
+
+/// Enables or disables recovering from hydration mismatches.
+///
+/// When enabled, an element that is missing from the server-rendered HTML or has the wrong tag is
+/// rendered on the client instead, along with all of its children. Otherwise, hydration panics if
+/// an element is missing and keeps using the mismatching element if the tag is wrong.
+///
+/// This is disabled by default.
+pub fn set_hydration_mismatch_recovery(enabled: bool) {
+    HYDRATION_MISMATCH_RECOVERY.with(|recovery| recovery.set(enabled));
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -184,4 +287,21 @@ mod tests {
         );
         assert_eq!(HydrationKey::parse("1"), None);
     }
+
+    #[test]
+    fn display_hydration_mismatch() {
+        let mismatch = HydrationMismatch {
+            key: Some(HydrationKey {
+                suspense: 0,
+                element: 3,
+            }),
+            expected: "<p>".to_string(),
+            found: "<div>".to_string(),
+            component_path: vec!["App", "Counter"],
+        };
+        assert_eq!(
+            mismatch.to_string(),
+            "hydration mismatch at `0.3` in `App > Counter`: expected <p>, found <div>"
+        );
+    }
 }
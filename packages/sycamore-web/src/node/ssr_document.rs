@@ -0,0 +1,326 @@
+//! Rendering complete HTML documents on the server.
+
+#[cfg(feature = "server-state")]
+use serde::Serialize;
+
+use super::*;
+#[cfg_ssr]
+use crate::server_state::ServerState;
+
+/// The parts of an HTML document around the app, used by [`render_document`] and friends.
+///
+/// The rendered document has the following structure:
+///
+/// ```html
+/// <!doctype html>
+/// <html lang="...">
+///   <head>
+///     <meta charset="utf-8">
+///     <!-- Tags from the head components, e.g. `Title`. -->
+///     <!-- The hydration script for the SSR mode. -->
+///     <!-- Everything passed to `head_html`. -->
+///     <!-- The wasm and JS assets. -->
+///   </head>
+///   <body><!-- The app, followed by the state. --></body>
+/// </html>
+/// ```
+///
+/// # Example
+/// ```
+/// # use sycamore::prelude::*;
+/// # use sycamore::web::{render_document, DocumentShell};
+/// # fn App() -> View { view! {} }
+/// let shell = DocumentShell::new()
+///     .lang("en")
+///     .head_html(r#"<link rel="stylesheet" href="/style.css">"#)
+///     .wasm("/pkg/app.js", "/pkg/app_bg.wasm");
+/// let html = render_document(&shell, App);
+/// ```
+#[derive(Debug, Clone, Default)]
+// The shell is only rendered in SSR mode.
+#[cfg_attr(all(target_arch = "wasm32", not(sycamore_force_ssr)), allow(dead_code))]
+pub struct DocumentShell {
+    lang: Option<Cow<'static, str>>,
+    head_html: Vec<Cow<'static, str>>,
+    wasm: Option<(Cow<'static, str>, Cow<'static, str>)>,
+    state: Vec<(String, String)>,
+}
+
+impl DocumentShell {
+    /// Creates a new shell with nothing but the bare document structure.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the `lang` attribute of the `<html>` element.
+    pub fn lang(mut self, lang: impl Into<Cow<'static, str>>) -> Self {
+        self.lang = Some(lang.into());
+        self
+    }
+
+    /// Appends raw HTML to the `<head>` element, e.g. stylesheets or analytics scripts.
+    ///
+    /// This is not escaped, so never pass user input to it.
+    pub fn head_html(mut self, html: impl Into<Cow<'static, str>>) -> Self {
+        self.head_html.push(html.into());
+        self
+    }
+
+    /// Loads and starts the app on the client. `js` is the URL of the JS glue generated by
+    /// `wasm-bindgen` (with `--target web`) and `wasm` is the URL of the `.wasm` binary.
+    ///
+    /// The wasm binary is preloaded so that it is fetched in parallel with the rest of the page.
+    pub fn wasm(
+        mut self,
+        js: impl Into<Cow<'static, str>>,
+        wasm: impl Into<Cow<'static, str>>,
+    ) -> Self {
+        self.wasm = Some((js.into(), wasm.into()));
+        self
+    }
+
+    /// Passes `value` to the client, where it can be read using [`use_server_state`] with the
+    /// same `key`. This is the same as calling [`provide_server_state`] at the start of the
+    /// render.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `value` cannot be serialized.
+    #[cfg(feature = "server-state")]
+    pub fn state<T: Serialize + ?Sized>(mut self, key: impl Into<String>, value: &T) -> Self {
+        let json = serde_json::to_string(value).expect("could not serialize server state");
+        self.state.push((key.into(), json));
+        self
+    }
+
+    /// Wraps `view` so that the state of the shell is provided before it is created.
+    #[cfg_ssr]
+    fn wrap_view(&self, view: impl FnOnce() -> View) -> impl FnOnce() -> View {
+        let state = self.state.clone();
+        move || {
+            let server_state = use_context::<ServerState>();
+            for (key, json) in state {
+                server_state.push(key, json);
+            }
+            view()
+        }
+    }
+
+    /// Renders everything up to and including the opening `<body>` tag.
+    #[cfg_ssr]
+    fn render_start(&self, mode: SsrMode, head: &str, buf: &mut String) {
+        buf.push_str("<!doctype html><html");
+        if let Some(lang) = &self.lang {
+            buf.push_str(" lang=\"");
+            html_escape::encode_double_quoted_attribute_to_string(lang, buf);
+            buf.push('"');
+        }
+        buf.push_str("><head><meta charset=\"utf-8\">");
+        buf.push_str(head);
+
+        let hydration_script = generate_hydration_script(mode);
+        if !hydration_script.is_empty() {
+            buf.push_str("<script>");
+            buf.push_str(hydration_script);
+            buf.push_str("</script>");
+        }
+        for html in &self.head_html {
+            buf.push_str(html);
+        }
+        if let Some((js, wasm)) = &self.wasm {
+            buf.push_str("<link rel=\"preload\" href=\"");
+            html_escape::encode_double_quoted_attribute_to_string(wasm, buf);
+            buf.push_str("\" as=\"fetch\" type=\"application/wasm\" crossorigin=\"\">");
+            buf.push_str(&format!(
+                "<script type=\"module\">import init from {js};init({{module_or_path:{wasm}}});</script>",
+                js = js_string_literal(js),
+                wasm = js_string_literal(wasm),
+            ));
+        }
+        buf.push_str("</head><body>");
+    }
+}
+
+/// Encodes `s` as a JS string literal that can be put inside of a `<script>` element.
+#[cfg_ssr]
+fn js_string_literal(s: &str) -> String {
+    // A JSON string is also a valid JS string. Escaping `<` makes sure that the string cannot
+    // close the script element.
+    serde_json::to_string(s).unwrap().replace('<', "\\u003c")
+}
+
+/// The end of the document after the app.
+#[cfg_ssr]
+const DOCUMENT_END: &str = "</body></html>";
+
+/// Renders a [`View`] into a complete HTML document, using `shell` for everything around it.
+///
+/// Like [`render_to_string`], this renders in [`SsrMode::Sync`]. The view is rendered into the
+/// `<body>` element, so it should be hydrated on the client with
+/// [`hydrate`](crate::hydrate). The tags that are added by head components such as [`Title`]
+/// are rendered into the `<head>` element.
+#[must_use]
+pub fn render_document(shell: &DocumentShell, view: impl FnOnce() -> View) -> String {
+    is_not_ssr! {
+        let _ = (shell, view);
+        panic!("`render_document` only available in SSR mode");
+    }
+    is_ssr! {
+        let mut body = String::new();
+        let head = render_sync(shell.wrap_view(view), &mut body).unwrap();
+        let mut buf = String::new();
+        shell.render_start(SsrMode::Sync, &head, &mut buf);
+        buf.push_str(&body);
+        buf.push_str(DOCUMENT_END);
+        buf
+    }
+}
+
+/// Like [`render_document`] but waits for all suspense boundaries to resolve, like
+/// [`render_to_string_await_suspense`].
+#[must_use]
+#[cfg(feature = "suspense")]
+pub async fn render_document_await_suspense(
+    shell: &DocumentShell,
+    view: impl FnOnce() -> View,
+) -> String {
+    is_not_ssr! {
+        let _ = (shell, view);
+        panic!("`render_document_await_suspense` only available in SSR mode");
+    }
+    is_ssr! {
+        let output = await_suspense(shell.wrap_view(view), None).await;
+        let mut buf = String::new();
+        shell.render_start(SsrMode::Blocking, &output.head, &mut buf);
+        buf.push_str(&output.html);
+        buf.push_str(DOCUMENT_END);
+        buf
+    }
+}
+
+/// Like [`render_document`] but streams the document, like [`render_to_string_stream`].
+///
+/// The first chunk contains the whole document up to the end of the initial HTML of the view.
+/// The resolved suspense fragments are streamed inside of the `<body>` element and the document
+/// is closed once all of them have been sent. Only the head tags that are added before the first
+/// chunk is rendered are included.
+///
+/// This requires an executor, just like [`render_to_string_stream`].
+#[cfg(feature = "suspense")]
+pub fn render_document_stream(
+    shell: &DocumentShell,
+    view: impl FnOnce() -> View,
+) -> impl futures::Stream<Item = String> + Send {
+    is_not_ssr! {
+        let _ = (shell, view);
+        panic!("`render_document_stream` only available in SSR mode");
+        #[allow(unreachable_code)] // TODO: never type cannot be coerced into `impl Stream` somehow.
+        futures::stream::empty()
+    }
+    is_ssr! {
+        let (RenderedView { head, body }, fragments) = render_stream(shell.wrap_view(view));
+        let mut initial = String::new();
+        shell.render_start(SsrMode::Streaming, &head, &mut initial);
+        initial.push_str(&body);
+        initial.push_str(SUSPENSE_REPLACE_SCRIPT);
+        async_stream::stream! {
+            yield initial;
+
+            for await fragment in fragments {
+                yield fragment;
+            }
+            yield DOCUMENT_END.to_string();
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg_ssr]
+mod tests {
+    use expect_test::expect;
+
+    use super::*;
+
+    #[component]
+    fn App() -> View {
+        view! {
+            Title(text="Hello")
+            p { "Hello, world!" }
+        }
+    }
+
+    #[test]
+    fn render_document_with_shell() {
+        let shell = DocumentShell::new()
+            .lang("en")
+            .head_html(r#"<link rel="stylesheet" href="/style.css">"#)
+            .wasm("/pkg/app.js", "/pkg/app_bg.wasm");
+        let html = render_document(&shell, App);
+        expect![[r#"<!doctype html><html lang="en"><head><meta charset="utf-8"><title data-sycamore-head="title">Hello</title><link rel="stylesheet" href="/style.css"><link rel="preload" href="/pkg/app_bg.wasm" as="fetch" type="application/wasm" crossorigin=""><script type="module">import init from "/pkg/app.js";init({module_or_path:"/pkg/app_bg.wasm"});</script></head><body><p data-hk="0.0">Hello, world!</p></body></html>"#]]
+            .assert_eq(&html);
+    }
+
+    #[cfg(feature = "server-state")]
+    #[test]
+    fn render_document_with_state() {
+        let shell = DocumentShell::new().state("user", "</script>");
+        let html = render_document(&shell, || {
+            let user = use_server_state::<String>("user").unwrap();
+            view! { p { (user) } }
+        });
+        expect![[r#"<!doctype html><html><head><meta charset="utf-8"></head><body><p data-hk="0.0">&lt;/script&gt;</p><script type="application/json" data-sycamore-state="user">"<\/script>"</script></body></html>"#]].assert_eq(&html);
+    }
+
+    #[test]
+    fn render_document_escapes_wasm_urls() {
+        let shell = DocumentShell::new().wasm("/pkg/</script>\"app.js", "/pkg/app\\bg.wasm");
+        let html = render_document(&shell, App);
+        expect![[r#"<!doctype html><html><head><meta charset="utf-8"><title data-sycamore-head="title">Hello</title><link rel="preload" href="/pkg/app\bg.wasm" as="fetch" type="application/wasm" crossorigin=""><script type="module">import init from "/pkg/\u003c/script>\"app.js";init({module_or_path:"/pkg/app\\bg.wasm"});</script></head><body><p data-hk="0.0">Hello, world!</p></body></html>"#]].assert_eq(&html);
+    }
+
+    #[cfg(feature = "suspense")]
+    #[tokio::test]
+    async fn render_document_await_suspense_adds_hydration_script() {
+        let html = render_document_await_suspense(&DocumentShell::new(), App).await;
+        expect![[r#"<!doctype html><html><head><meta charset="utf-8"><title data-sycamore-head="title">Hello</title><script>window.__sycamore_ssr_mode='blocking'</script></head><body><p data-hk="0.0">Hello, world!</p></body></html>"#]]
+            .assert_eq(&html);
+    }
+
+    #[cfg(feature = "suspense")]
+    #[test]
+    fn render_document_stream_closes_document() {
+        use std::time::Duration;
+
+        use futures::StreamExt;
+        use sycamore_futures::{sleep, TestExecutor};
+
+        #[component]
+        async fn Delayed() -> View {
+            sleep(Duration::from_secs(1)).await;
+            view! { "delayed" }
+        }
+
+        let executor = TestExecutor::new();
+        let stream = executor.enter(|| {
+            render_document_stream(&DocumentShell::new(), || {
+                view! {
+                    Suspense(fallback=|| "loading".into()) {
+                        Delayed {}
+                    }
+                }
+            })
+        });
+        executor.advance_time(Duration::from_secs(1));
+        let chunks = executor.run(stream.collect::<Vec<_>>()).unwrap();
+        assert!(chunks[0].starts_with(
+            "<!doctype html><html><head><meta charset=\"utf-8\"><script>window.__sycamore_ssr_mode='streaming'</script></head><body>"
+        ));
+        expect![[r#"
+            [
+                "<template id=\"sycamore-suspense-1\"><!--/--><!--/-->delayed<!--/--><!--/--></template><script>__sycamore_suspense(1)</script>",
+                "</body></html>",
+            ]
+        "#]]
+        .assert_debug_eq(&&chunks[1..]);
+    }
+}
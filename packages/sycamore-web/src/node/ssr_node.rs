@@ -1,5 +1,6 @@
 use std::any::{Any, TypeId};
 use std::collections::HashSet;
+use std::fmt;
 use std::sync::{Arc, Mutex};
 
 use once_cell::sync::Lazy;
@@ -92,11 +93,18 @@ impl ViewHtmlNode for SsrNode {
         } else {
             None
         };
+        // The tags from the head components are rendered at the start of the `head` element.
+        let mut children = Vec::new();
+        if tag == "head" {
+            if let Some(head) = try_use_context::<HeadTags>() {
+                children.push(head.create_node());
+            }
+        }
         Self::Element {
             tag,
             attributes: Vec::new(),
             bool_attributes: Vec::new(),
-            children: Vec::new(),
+            children,
             inner_html: None,
             hk_key,
         }
@@ -136,18 +144,64 @@ impl ViewHtmlNode for SsrNode {
         match self {
             Self::Element {
                 bool_attributes, ..
-            } => bool_attributes.push((name, value.evaluate())),
+            } => set_bool_attribute(bool_attributes, name, value.evaluate()),
             _ => panic!("can only set attribute on an element"),
         }
     }
 
-    fn set_property(&mut self, _name: Cow<'static, str>, _value: MaybeDyn<JsValue>) {
-        // Noop in SSR mode.
+    fn set_property(&mut self, name: Cow<'static, str>, value: MaybeDyn<PropertyValue>) {
+        let Self::Element {
+            tag,
+            attributes,
+            bool_attributes,
+            children,
+            ..
+        } = self
+        else {
+            panic!("can only set property on an element");
+        };
+        // Only properties that reflect the initial state of an element are rendered. All the
+        // other properties, e.g. `indeterminate`, are only set on the client.
+        match name.as_ref() {
+            // `valueAsNumber` is only the same as `value` for number inputs. For date and time
+            // inputs, it is a timestamp. The `type` attribute therefore needs to be set before.
+            "valueAsNumber"
+                if !attributes.iter().any(|(name, ty)| {
+                    name == "type" && matches!(ty.as_ref(), "number" | "range")
+                }) => {}
+            "value" | "valueAsNumber" => {
+                let Some(value) = property_to_string(value.evaluate()) else {
+                    return;
+                };
+                match tag.as_ref() {
+                    // The value of a textarea is its text content.
+                    "textarea" => *children = vec![Self::TextStatic { text: value }],
+                    // The value of a select is set by the `selected` attribute of its options.
+                    "select" => {}
+                    _ => {
+                        attributes.retain(|(name, _)| name != "value");
+                        attributes.push(("value".into(), value));
+                    }
+                }
+            }
+            "checked" | "selected" | "disabled" | "multiple" | "required" | "hidden" => {
+                if let Some(value) = property_to_bool(value.evaluate()) {
+                    set_bool_attribute(bool_attributes, name, value);
+                }
+            }
+            "readOnly" => {
+                if let Some(value) = property_to_bool(value.evaluate()) {
+                    set_bool_attribute(bool_attributes, "readonly".into(), value);
+                }
+            }
+            _ => {}
+        }
     }
 
     fn set_event_handler(
         &mut self,
         _name: Cow<'static, str>,
+        _options: events::EventOptions,
         _handler: impl FnMut(web_sys::Event) + 'static,
     ) {
         // Noop in SSR mode.
@@ -171,6 +225,41 @@ impl ViewHtmlNode for SsrNode {
     }
 }
 
+/// Sets a boolean attribute, replacing the previous value if it was already set, e.g. by both the
+/// `checked` attribute and the `checked` property.
+fn set_bool_attribute(
+    bool_attributes: &mut Vec<(Cow<'static, str>, bool)>,
+    name: Cow<'static, str>,
+    value: bool,
+) {
+    bool_attributes.retain(|(n, _)| *n != name);
+    bool_attributes.push((name, value));
+}
+
+/// Converts a property value to a string like JS does when setting a string property. Returns
+/// `None` for values that cannot be read in SSR mode.
+fn property_to_string(value: PropertyValue) -> Option<Cow<'static, str>> {
+    match value {
+        PropertyValue::Bool(value) => Some(value.to_string().into()),
+        // Setting `valueAsNumber` to `NaN` clears the value.
+        PropertyValue::Number(value) if value.is_nan() => Some("".into()),
+        PropertyValue::Number(value) => Some(value.to_string().into()),
+        PropertyValue::String(value) => Some(value),
+        PropertyValue::Js(_) => None,
+    }
+}
+
+/// Converts a property value to a boolean like JS does when setting a boolean property. Returns
+/// `None` for values that cannot be read in SSR mode.
+fn property_to_bool(value: PropertyValue) -> Option<bool> {
+    match value {
+        PropertyValue::Bool(value) => Some(value),
+        PropertyValue::Number(value) => Some(value != 0.0 && !value.is_nan()),
+        PropertyValue::String(value) => Some(!value.is_empty()),
+        PropertyValue::Js(_) => None,
+    }
+}
+
 /// A list of all the void HTML elements. We need this to know how to render them to a string.
 static VOID_ELEMENTS: Lazy<HashSet<&'static str>> = Lazy::new(|| {
     [
@@ -183,6 +272,17 @@ static VOID_ELEMENTS: Lazy<HashSet<&'static str>> = Lazy::new(|| {
 
 /// Recursively render `node` by appending to `buf`.
 pub(crate) fn render_recursive(node: &SsrNode, buf: &mut String) {
+    // Writing into a `String` cannot fail.
+    write_recursive(node, buf).unwrap();
+}
+
+/// Recursively render a [`View`] to a string by calling `render_recursive` on each node.
+pub(crate) fn render_recursive_view(view: &View, buf: &mut String) {
+    write_recursive_view(view, buf).unwrap();
+}
+
+/// Recursively render `node` into `w`.
+pub(crate) fn write_recursive(node: &SsrNode, w: &mut impl fmt::Write) -> fmt::Result {
     match node {
         SsrNode::Element {
             tag,
@@ -192,28 +292,26 @@ pub(crate) fn render_recursive(node: &SsrNode, buf: &mut String) {
             inner_html,
             hk_key,
         } => {
-            buf.push('<');
-            buf.push_str(tag);
+            w.write_char('<')?;
+            w.write_str(tag)?;
             for (name, value) in attributes {
-                buf.push(' ');
-                buf.push_str(name);
-                buf.push_str("=\"");
-                html_escape::encode_double_quoted_attribute_to_string(value, buf);
-                buf.push('"');
+                w.write_char(' ')?;
+                w.write_str(name)?;
+                w.write_str("=\"")?;
+                w.write_str(&html_escape::encode_double_quoted_attribute(value))?;
+                w.write_char('"')?;
             }
             for (name, value) in bool_attributes {
                 if *value {
-                    buf.push(' ');
-                    buf.push_str(name);
+                    w.write_char(' ')?;
+                    w.write_str(name)?;
                 }
             }
 
             if let Some(hk_key) = hk_key {
-                buf.push_str(" data-hk=\"");
-                buf.push_str(&hk_key.to_string());
-                buf.push('"');
+                write!(w, " data-hk=\"{hk_key}\"")?;
             }
-            buf.push('>');
+            w.write_char('>')?;
 
             let is_void = VOID_ELEMENTS.contains(tag.as_ref());
 
@@ -222,48 +320,50 @@ pub(crate) fn render_recursive(node: &SsrNode, buf: &mut String) {
                     children.is_empty() && inner_html.is_none(),
                     "void elements cannot have children or inner_html"
                 );
-                return;
+                return Ok(());
             }
             if let Some(inner_html) = inner_html {
                 assert!(
                     children.is_empty(),
                     "inner_html and children are mutually exclusive"
                 );
-                buf.push_str(inner_html);
+                w.write_str(inner_html)?;
             } else {
                 for child in children {
-                    render_recursive(child, buf);
+                    write_recursive(child, w)?;
                 }
             }
 
             if !is_void {
-                buf.push_str("</");
-                buf.push_str(tag);
-                buf.push('>');
+                w.write_str("</")?;
+                w.write_str(tag)?;
+                w.write_char('>')?;
             }
         }
         SsrNode::TextDynamic { text } => {
-            buf.push_str("<!--t-->"); // For dynamic text, add a marker for hydrating it.
-            html_escape::encode_text_to_string(text.lock().unwrap().as_str(), buf);
-            buf.push_str("<!-->"); // End of dynamic text.
+            w.write_str("<!--t-->")?; // For dynamic text, add a marker for hydrating it.
+            w.write_str(&html_escape::encode_text(text.lock().unwrap().as_str()))?;
+            w.write_str("<!-->")?; // End of dynamic text.
         }
         SsrNode::TextStatic { text } => {
-            html_escape::encode_text_to_string(text, buf);
+            w.write_str(&html_escape::encode_text(text))?;
         }
         SsrNode::Marker => {
-            buf.push_str("<!--/-->");
+            w.write_str("<!--/-->")?;
         }
         SsrNode::Dynamic { view } => {
-            render_recursive_view(&view.lock().unwrap(), buf);
+            write_recursive_view(&view.lock().unwrap(), w)?;
         }
     }
+    Ok(())
 }
 
-/// Recursively render a [`View`] to a string by calling `render_recursive` on each node.
-pub(crate) fn render_recursive_view(view: &View, buf: &mut String) {
+/// Recursively render a [`View`] into `w` by calling `write_recursive` on each node.
+pub(crate) fn write_recursive_view(view: &View, w: &mut impl fmt::Write) -> fmt::Result {
     for node in &view.nodes {
-        render_recursive(node, buf);
+        write_recursive(node, w)?;
     }
+    Ok(())
 }
 
 #[cfg(test)]
@@ -335,15 +435,85 @@ mod tests {
 
     #[test]
     fn bind() {
-        // bind always attaches to a JS prop so it is not rendered in SSR.
         check(
             move || {
-                let value = create_signal(String::new());
+                let value = create_signal("Hello".to_string());
                 sycamore_macro::view! {
                     input(bind:value=value)
                 }
             },
-            expect![[r#"<input data-hk="0.0">"#]],
+            expect![[r#"<input value="Hello" data-hk="0.0">"#]],
+        );
+        check(
+            move || {
+                let value = create_signal(1.5);
+                sycamore_macro::view! {
+                    input(r#type="number", value="0", bind:valueAsNumber=value)
+                }
+            },
+            expect![[r#"<input type="number" value="1.5" data-hk="0.0">"#]],
+        );
+        // For date inputs, `valueAsNumber` is a timestamp and not the value.
+        check(
+            move || {
+                let value = create_signal(0.0);
+                sycamore_macro::view! {
+                    input(r#type="date", value="1970-01-01", bind:valueAsNumber=value)
+                }
+            },
+            expect![[r#"<input type="date" value="1970-01-01" data-hk="0.0">"#]],
+        );
+        check(
+            move || {
+                let checked = create_signal(true);
+                sycamore_macro::view! {
+                    input(r#type="checkbox", bind:checked=checked)
+                }
+            },
+            expect![[r#"<input type="checkbox" checked data-hk="0.0">"#]],
+        );
+        check(
+            move || {
+                let checked = create_signal(false);
+                sycamore_macro::view! {
+                    input(r#type="checkbox", checked=true, bind:checked=checked)
+                }
+            },
+            expect![[r#"<input type="checkbox" data-hk="0.0">"#]],
+        );
+        check(
+            move || {
+                let value = create_signal("<Hello>".to_string());
+                sycamore_macro::view! {
+                    textarea(bind:value=value)
+                }
+            },
+            expect![[r#"<textarea data-hk="0.0">&lt;Hello&gt;</textarea>"#]],
+        );
+    }
+
+    #[test]
+    fn prop() {
+        check(
+            move || {
+                let selected = create_signal(1);
+                sycamore_macro::view! {
+                    select {
+                        option(prop:selected=selected.get() == 0) { "A" }
+                        option(prop:selected=selected.get() == 1) { "B" }
+                    }
+                }
+            },
+            expect![[r#"<select data-hk="0.0"><option data-hk="0.1">A</option><option selected data-hk="0.2">B</option></select>"#]],
+        );
+        // `indeterminate` has no attribute equivalent and is only set on the client.
+        check(
+            move || {
+                sycamore_macro::view! {
+                    input(r#type="checkbox", prop:indeterminate=true, prop:readOnly=true)
+                }
+            },
+            expect![[r#"<input type="checkbox" readonly data-hk="0.0">"#]],
         );
     }
 
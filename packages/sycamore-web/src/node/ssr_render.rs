@@ -1,4 +1,6 @@
 use super::*;
+#[cfg_ssr]
+use crate::server_state::ServerState;
 
 /// The mode in which SSR is being run.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -19,6 +21,9 @@ pub enum SsrMode {
 }
 
 /// Render a [`View`] into a static [`String`]. Useful for rendering to a string on the server side.
+///
+/// The tags that are added by head components such as [`Title`] are not included unless the view
+/// has a `head` element. Use [`render_to_string_with_head`] to get them separately.
 #[must_use]
 pub fn render_to_string(view: impl FnOnce() -> View) -> String {
     is_not_ssr! {
@@ -26,19 +31,35 @@ pub fn render_to_string(view: impl FnOnce() -> View) -> String {
         panic!("`render_to_string` only available in SSR mode");
     }
     is_ssr! {
-        use std::cell::LazyCell;
+        let mut buf = String::new();
+        render_sync(view, &mut buf).unwrap();
+        buf
+    }
+}
 
-        thread_local! {
-            /// Use a static variable here so that we can reuse the same root for multiple calls to
-            /// this function.
-            static SSR_ROOT: LazyCell<RootHandle> = LazyCell::new(|| create_root(|| {}));
-        }
-        SSR_ROOT.with(|root| {
-            root.dispose();
-            root.run_in(|| {
-                render_to_string_in_scope(view)
-            })
-        })
+/// The HTML of a rendered [`View`] and of the head tags that were added while rendering it. See
+/// [`render_to_string_with_head`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenderedView {
+    /// The HTML of the tags that were added by the head components, e.g. [`Title`]. These are
+    /// already included in `body` if the view has a `head` element.
+    pub head: String,
+    /// The rendered HTML of the view.
+    pub body: String,
+}
+
+/// Like [`render_to_string`] but also returns the HTML of the head tags, e.g. from [`Title`], so
+/// that they can be inserted into a document that is not rendered by Sycamore.
+#[must_use]
+pub fn render_to_string_with_head(view: impl FnOnce() -> View) -> RenderedView {
+    is_not_ssr! {
+        let _ = view;
+        panic!("`render_to_string_with_head` only available in SSR mode");
+    }
+    is_ssr! {
+        let mut body = String::new();
+        let head = render_sync(view, &mut body).unwrap();
+        RenderedView { head, body }
     }
 }
 
@@ -53,17 +74,185 @@ pub fn render_to_string_in_scope(view: impl FnOnce() -> View) -> String {
     }
     is_ssr! {
         let mut buf = String::new();
+        render_sync_in_scope(view, &mut buf).unwrap();
+        buf
+    }
+}
 
-        let handle = create_child_scope(|| {
-            provide_context(HydrationRegistry::new());
-            provide_context(SsrMode::Sync);
+/// Render a [`View`] directly into a [`fmt::Write`] sink instead of building a [`String`] first.
+///
+/// This is otherwise the same as [`render_to_string`].
+pub fn render_to_fmt_writer(view: impl FnOnce() -> View, w: &mut impl fmt::Write) -> fmt::Result {
+    is_not_ssr! {
+        let _ = (view, w);
+        panic!("`render_to_fmt_writer` only available in SSR mode");
+    }
+    is_ssr! {
+        render_sync(view, w).map(|_| ())
+    }
+}
 
-            let prev = IS_HYDRATING.replace(true);
-            let view = view();
-            IS_HYDRATING.set(prev);
-            ssr_node::render_recursive_view(&view, &mut buf);
-        });
-        handle.dispose();
+/// Render a [`View`] directly into an [`io::Write`](std::io::Write) sink, e.g. a socket or a
+/// response body, instead of building the whole HTML [`String`] first.
+///
+/// The HTML is written in many small pieces, so `w` should usually be buffered. Use
+/// [`render_to_writer_chunked`] to control how much is written at once.
+///
+/// This is otherwise the same as [`render_to_string`].
+pub fn render_to_writer(
+    view: impl FnOnce() -> View,
+    w: &mut impl std::io::Write,
+) -> std::io::Result<()> {
+    is_not_ssr! {
+        let _ = (view, w);
+        panic!("`render_to_writer` only available in SSR mode");
+    }
+    is_ssr! {
+        let mut w = IoWriter { inner: w, chunk: String::new(), chunk_size: 0, error: None };
+        let result = render_sync(view, &mut w);
+        w.finish(result)
+    }
+}
+
+/// Like [`render_to_writer`] but collects the HTML into chunks of at least `chunk_size` bytes.
+/// Every chunk is written to `w` at once, after which `w` is flushed.
+///
+/// This keeps the memory usage bounded by roughly `chunk_size` while still sending the response
+/// in reasonably sized pieces.
+pub fn render_to_writer_chunked(
+    view: impl FnOnce() -> View,
+    w: &mut impl std::io::Write,
+    chunk_size: usize,
+) -> std::io::Result<()> {
+    is_not_ssr! {
+        let _ = (view, w, chunk_size);
+        panic!("`render_to_writer_chunked` only available in SSR mode");
+    }
+    is_ssr! {
+        let mut w = IoWriter {
+            inner: w,
+            chunk: String::with_capacity(chunk_size),
+            chunk_size,
+            error: None,
+        };
+        let result = render_sync(view, &mut w);
+        w.finish(result)
+    }
+}
+
+/// Adapts an [`io::Write`](std::io::Write) to a [`fmt::Write`], optionally collecting the
+/// output into chunks.
+#[cfg_ssr]
+struct IoWriter<'a, W> {
+    inner: &'a mut W,
+    chunk: String,
+    /// If `0`, everything is written directly to `inner`.
+    chunk_size: usize,
+    /// [`fmt::Error`] does not carry any information so the actual error is stored here.
+    error: Option<std::io::Error>,
+}
+
+#[cfg_ssr]
+impl<W: std::io::Write> IoWriter<'_, W> {
+    fn write_chunk(&mut self) -> std::io::Result<()> {
+        self.inner.write_all(self.chunk.as_bytes())?;
+        self.inner.flush()?;
+        self.chunk.clear();
+        Ok(())
+    }
+
+    /// Writes the last chunk and returns the I/O error that caused `result` to fail, if any.
+    fn finish(mut self, result: Result<String, fmt::Error>) -> std::io::Result<()> {
+        match result {
+            Ok(_) if self.chunk.is_empty() => Ok(()),
+            Ok(_) => self.write_chunk(),
+            Err(_) => Err(self.error.take().expect("only I/O errors are possible")),
+        }
+    }
+}
+
+#[cfg_ssr]
+impl<W: std::io::Write> fmt::Write for IoWriter<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let result = if self.chunk_size == 0 {
+            self.inner.write_all(s.as_bytes())
+        } else {
+            self.chunk.push_str(s);
+            if self.chunk.len() >= self.chunk_size {
+                self.write_chunk()
+            } else {
+                Ok(())
+            }
+        };
+        result.map_err(|err| {
+            self.error = Some(err);
+            fmt::Error
+        })
+    }
+}
+
+/// Implementation for [`render_to_string`] and friends. Renders the view into `w` and returns the
+/// HTML of the head tags.
+#[cfg_ssr]
+pub(crate) fn render_sync(
+    view: impl FnOnce() -> View,
+    w: &mut impl fmt::Write,
+) -> Result<String, fmt::Error> {
+    use std::cell::LazyCell;
+
+    thread_local! {
+        /// Use a static variable here so that we can reuse the same root for multiple calls to
+        /// this function.
+        static SSR_ROOT: LazyCell<RootHandle> = LazyCell::new(|| create_root(|| {}));
+    }
+    SSR_ROOT.with(|root| {
+        root.dispose();
+        root.run_in(|| render_sync_in_scope(view, w))
+    })
+}
+
+/// Implementation for [`render_to_string_in_scope`].
+#[cfg_ssr]
+fn render_sync_in_scope(
+    view: impl FnOnce() -> View,
+    w: &mut impl fmt::Write,
+) -> Result<String, fmt::Error> {
+    let mut result = Ok(());
+    let head_tags = HeadTags::new();
+    let server_state = ServerState::new();
+
+    let handle = create_child_scope(|| {
+        provide_context(HydrationRegistry::new());
+        provide_context(SsrMode::Sync);
+        provide_context(head_tags);
+        provide_context(server_state);
+
+        let prev = IS_HYDRATING.replace(true);
+        let view = view();
+        IS_HYDRATING.set(prev);
+        result = ssr_node::write_recursive_view(&view, w)
+            .and_then(|_| w.write_str(&server_state.render_pending()));
+    });
+    let head = head_tags.render_to_string();
+    handle.dispose();
+    result.map(|_| head)
+}
+
+/// Render an already created [`View`] into a [`String`].
+///
+/// Unlike [`render_to_string`], this does not create a new reactive scope and can be called
+/// multiple times on the same view. Since dynamic nodes are rendered with their current value,
+/// this is useful for inspecting the output of a view in between the steps of a `TestExecutor`
+/// in tests.
+#[must_use]
+pub fn render_view_to_string(view: &View) -> String {
+    is_not_ssr! {
+        let _ = view;
+        panic!("`render_view_to_string` only available in SSR mode");
+    }
+    is_ssr! {
+        let mut buf = String::new();
+        ssr_node::render_recursive_view(view, &mut buf);
         buf
     }
 }
@@ -72,7 +261,8 @@ pub fn render_to_string_in_scope(view: impl FnOnce() -> View) -> String {
 /// resolve. Useful for rendering to a string on the server side.
 ///
 /// This sets the SSR mode to "blocking" mode. This means that rendering will wait until suspense
-/// is resolved before returning.
+/// is resolved before returning. Use [`render_to_string_await_suspense_with_timeout`] to stop
+/// waiting after a deadline.
 ///
 /// # Example
 /// ```
@@ -96,50 +286,157 @@ pub async fn render_to_string_await_suspense(f: impl FnOnce() -> View) -> String
         panic!("`render_to_string` only available in SSR mode");
     }
     is_ssr! {
-        use std::cell::LazyCell;
-        use futures::channel::oneshot;
-        use sycamore_futures::{provide_executor_scope, use_is_loading_global};
-
-        thread_local! {
-            /// Use a static variable here so that we can reuse the same root for multiple calls to
-            /// this function.
-            static SSR_ROOT: LazyCell<RootHandle> = LazyCell::new(|| create_root(|| {}));
-        }
+        await_suspense(f, None).await.html
+    }
+}
+
+/// The output of [`render_to_string_await_suspense_with_timeout`].
+#[cfg(feature = "suspense")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AwaitSuspenseOutput {
+    /// The rendered HTML.
+    pub html: String,
+    /// The HTML of the tags that were added by the head components, e.g. [`Title`]. These are
+    /// already included in `html` if the view has a `head` element.
+    pub head: String,
+    /// The keys of all the suspense boundaries that timed out, either because of the deadline or
+    /// because of their own `timeout` prop. These are rendered with their fallback and are loaded
+    /// on the client side instead.
+    ///
+    /// The key is the value of the `data-key` attribute of the `<suspense-start>` element of the
+    /// boundary.
+    pub timed_out: Vec<NonZeroU32>,
+}
+
+/// Like [`render_to_string_await_suspense`] but stops waiting for suspense boundaries once
+/// `timeout` has passed.
+///
+/// Boundaries that are still loading at the deadline are rendered with their fallback instead, as
+/// in [`SsrMode::Sync`], and are loaded on the client side. The timer uses
+/// [`sleep`](sycamore_futures::sleep) and therefore requires the tokio time driver to be enabled.
+///
+/// # Example
+/// ```
+/// # use std::time::Duration;
+/// # use sycamore::prelude::*;
+/// # use sycamore::web::render_to_string_await_suspense_with_timeout;
+/// #[component]
+/// async fn AsyncComponent() -> View {
+///     // Do some async operations.   
+///     # view! {}
+/// }
+///
+/// # tokio_test::block_on(async move {
+/// let output =
+///     render_to_string_await_suspense_with_timeout(AsyncComponent, Duration::from_secs(2)).await;
+/// if !output.timed_out.is_empty() {
+///     eprintln!("{} suspense boundaries timed out", output.timed_out.len());
+/// }
+/// # })
+/// ```
+#[must_use]
+#[cfg(feature = "suspense")]
+pub async fn render_to_string_await_suspense_with_timeout(
+    f: impl FnOnce() -> View,
+    timeout: std::time::Duration,
+) -> AwaitSuspenseOutput {
+    is_not_ssr! {
+        let _ = (f, timeout);
+        panic!("`render_to_string_await_suspense_with_timeout` only available in SSR mode");
+    }
+    is_ssr! {
+        await_suspense(f, Some(timeout)).await
+    }
+}
 
-        let mut handle: Option<NodeHandle> = None;
-        let (tx, rx) = oneshot::channel();
-        let mut tx = Some(tx);
-        let mut view = View::default();
-
-        let is_hydrating = IS_HYDRATING.replace(true);
-        provide_executor_scope(async {
-            SSR_ROOT.with(|root| {
-                root.dispose();
-                root.run_in(|| {
-                    handle = Some(create_child_scope(|| {
-                        provide_context(HydrationRegistry::new());
-                        provide_context(SsrMode::Blocking);
-
-                        view = f();
-                    }));
-
-                    // Now we wait until all suspense has resolved.
-                    create_effect(move || {
-                        if !use_is_loading_global() {
-                            if let Some(tx) = tx.take() {
-                                tx.send(()).ok().unwrap();
-                            }
+/// Implementation for [`render_to_string_await_suspense`] and
+/// [`render_to_string_await_suspense_with_timeout`].
+#[cfg_ssr]
+#[cfg(feature = "suspense")]
+pub(crate) async fn await_suspense(
+    f: impl FnOnce() -> View,
+    timeout: Option<std::time::Duration>,
+) -> AwaitSuspenseOutput {
+    use std::cell::LazyCell;
+    use std::pin::pin;
+
+    use futures::channel::oneshot;
+    use futures::future::{select, Either};
+    use sycamore_futures::{provide_executor_scope, sleep, use_is_loading_global};
+
+    thread_local! {
+        /// Use a static variable here so that we can reuse the same root for multiple calls to
+        /// this function.
+        static SSR_ROOT: LazyCell<RootHandle> = LazyCell::new(|| create_root(|| {}));
+    }
+
+    let mut handle: Option<NodeHandle> = None;
+    let mut deadline: Option<SuspenseDeadline> = None;
+    let mut head_tags: Option<HeadTags> = None;
+    let mut server_state: Option<ServerState> = None;
+    let (tx, rx) = oneshot::channel();
+    let mut tx = Some(tx);
+    let mut view = View::default();
+    let mut buf = String::new();
+    let mut head = String::new();
+
+    let is_hydrating = IS_HYDRATING.replace(true);
+    provide_executor_scope(async {
+        SSR_ROOT.with(|root| {
+            root.dispose();
+            root.run_in(|| {
+                let suspense_deadline = SuspenseDeadline {
+                    expired: create_signal(false),
+                    timed_out: create_signal(Vec::new()),
+                };
+                deadline = Some(suspense_deadline);
+                head_tags = Some(HeadTags::new());
+                server_state = Some(ServerState::new());
+                handle = Some(create_child_scope(|| {
+                    provide_context(HydrationRegistry::new());
+                    provide_context(SsrMode::Blocking);
+                    provide_context(suspense_deadline);
+                    provide_context(head_tags.unwrap());
+                    provide_context(server_state.unwrap());
+
+                    view = f();
+                }));
+
+                // Now we wait until all suspense has resolved.
+                create_effect(move || {
+                    if !use_is_loading_global() {
+                        if let Some(tx) = tx.take() {
+                            tx.send(()).ok().unwrap();
                         }
-                    });
+                    }
                 });
             });
-            rx.await.unwrap();
-            handle.unwrap().dispose();
-            IS_HYDRATING.set(is_hydrating);
-        }).await;
-        let mut buf = String::new();
+        });
+        match timeout {
+            Some(timeout) => match select(rx, pin!(sleep(timeout))).await {
+                Either::Left((result, _)) => result.unwrap(),
+                Either::Right(((), _)) => {
+                    // Give up on all the boundaries that are still loading. They are replaced with
+                    // their fallbacks synchronously. Do not wait for the global loading state
+                    // since tasks in detached suspense scopes, e.g. in a `Transition`, are never
+                    // abandoned and would keep the render from ever finishing.
+                    deadline.unwrap().expired.set(true);
+                }
+            },
+            None => rx.await.unwrap(),
+        }
+        // Render before disposing so that the head tags are still there.
         ssr_node::render_recursive_view(&view, &mut buf);
-        buf
+        buf.push_str(&server_state.unwrap().render_pending());
+        head = head_tags.unwrap().render_to_string();
+        handle.unwrap().dispose();
+        IS_HYDRATING.set(is_hydrating);
+    })
+    .await;
+    AwaitSuspenseOutput {
+        html: buf,
+        head,
+        timed_out: deadline.unwrap().timed_out.get_clone_untracked(),
     }
 }
 
@@ -151,6 +448,9 @@ pub async fn render_to_string_await_suspense(f: impl FnOnce() -> View) -> String
 /// The streamed suspense fragments are in the form of HTML template elements and a small script
 /// that moves the template elements into the right area of the DOM.
 ///
+/// The tags that are added by head components such as [`Title`] are not included unless the view
+/// has a `head` element. Use [`render_to_string_stream_with_head`] to get them separately.
+///
 /// # Executor
 ///
 /// This function (unlike [`render_to_string_await_suspense`]) does not automatically create an
@@ -210,74 +510,122 @@ pub fn render_to_string_stream(
         futures::stream::empty()
     }
     is_ssr! {
-        use std::cell::{LazyCell, RefCell};
-        use std::rc::Rc;
+        render_to_string_stream_with_head(view).1
+    }
+}
 
-        use futures::{SinkExt, StreamExt};
-        use futures::stream::FuturesUnordered;
+/// Like [`render_to_string_stream`] but also returns the HTML of the head tags, e.g. from
+/// [`Title`], so that they can be inserted into a document that is not rendered by Sycamore.
+///
+/// Only the head tags that were added during the initial render are included. Head tags that are
+/// added once a suspense boundary has resolved are not.
+#[cfg(feature = "suspense")]
+pub fn render_to_string_stream_with_head(
+    view: impl FnOnce() -> View,
+) -> (String, impl futures::Stream<Item = String> + Send) {
+    is_not_ssr! {
+        let _ = view;
+        panic!("`render_to_string_stream_with_head` only available in SSR mode");
+        #[allow(unreachable_code)] // TODO: never type cannot be coerced into `impl Stream` somehow.
+        (String::new(), futures::stream::empty())
+    }
+    is_ssr! {
+        let (RenderedView { head, body }, fragments) = render_stream(view);
+        let stream = async_stream::stream! {
+            let mut initial = String::new();
+            initial.push_str("<!doctype html>");
+            initial.push_str(&body);
+            initial.push_str(SUSPENSE_REPLACE_SCRIPT);
+            yield initial;
 
-        thread_local! {
-            /// Use a static variable here so that we can reuse the same root for multiple calls to
-            /// this function.
-            static SSR_ROOT: LazyCell<RootHandle> = LazyCell::new(|| create_root(|| {}));
-        }
-        IS_HYDRATING.set(true);
-        let mut buf = String::new();
-        let futures = Rc::new(RefCell::new(FuturesUnordered::new()));
-        let (mut tx, mut rx) = futures::channel::mpsc::unbounded();
+            for await fragment in fragments {
+                yield fragment;
+            }
+        };
+        (head, stream)
+    }
+}
 
-        SSR_ROOT.with(|root| {
-            root.dispose();
-            root.run_in(|| {
-                // We run this in a new scope so that we can dispose everything after we render it.
-                provide_context(HydrationRegistry::new());
-                provide_context(SsrMode::Streaming);
-                let suspense_state = SuspenseStream { futures: futures.clone() };
+// ```js
+// function __sycamore_suspense(key) {
+//   let start = document.querySelector(`suspense-start[data-key="${key}"]`)
+//   let end = document.querySelector(`suspense-end[data-key="${key}"]`)
+//   let template = document.getElementById(`sycamore-suspense-${key}`)
+//   start.parentNode.insertBefore(template.content, start)
+//   while (start.nextSibling != end) {
+//     start.parentNode.removeChild(start.nextSibling)
+//   }
+// }
+// ```
+#[cfg_ssr]
+#[cfg(feature = "suspense")]
+pub(crate) static SUSPENSE_REPLACE_SCRIPT: &str = r#"<script>function __sycamore_suspense(e){let s=document.querySelector(`suspense-start[data-key="${e}"]`),n=document.querySelector(`suspense-end[data-key="${e}"]`),r=document.getElementById(`sycamore-suspense-${e}`);for(s.parentNode.insertBefore(r.content,s);s.nextSibling!=n;)s.parentNode.removeChild(s.nextSibling);}</script>"#;
 
-                provide_context(suspense_state);
+/// Implementation for [`render_to_string_stream`] and [`render_document_stream`].
+///
+/// Returns the initial HTML and a stream of the suspense fragments.
+#[cfg_ssr]
+#[cfg(feature = "suspense")]
+pub(crate) fn render_stream(
+    view: impl FnOnce() -> View,
+) -> (RenderedView, impl futures::Stream<Item = String> + Send) {
+    use std::cell::{LazyCell, RefCell};
+    use std::rc::Rc;
 
-                let view = view();
-                ssr_node::render_recursive_view(&view, &mut buf);
+    use futures::stream::FuturesUnordered;
+    use futures::{SinkExt, StreamExt};
 
-                // Keep a buffer of all futures being polled. This is to avoid holding onto a lock
-                // over a wait point causing potential deadlocks.
-                let mut pending_futures = futures.take();
-                sycamore_futures::spawn_local_scoped(async move {
-                    while let Some(fragment) = pending_futures.next().await {
-                        tx.send(fragment).await.unwrap();
+    thread_local! {
+        /// Use a static variable here so that we can reuse the same root for multiple calls to
+        /// this function.
+        static SSR_ROOT: LazyCell<RootHandle> = LazyCell::new(|| create_root(|| {}));
+    }
+    IS_HYDRATING.set(true);
+    let mut body = String::new();
+    let mut head = String::new();
+    let futures = Rc::new(RefCell::new(FuturesUnordered::new()));
+    let (mut tx, rx) = futures::channel::mpsc::unbounded();
 
-                        // There can be more futures now. Add them to pending_futures.
-                        pending_futures.extend(futures.take());
-                    }
-                });
+    SSR_ROOT.with(|root| {
+        root.dispose();
+        root.run_in(|| {
+            // We run this in a new scope so that we can dispose everything after we render it.
+            provide_context(HydrationRegistry::new());
+            provide_context(SsrMode::Streaming);
+            let head_tags = HeadTags::new();
+            provide_context(head_tags);
+            let server_state = ServerState::new();
+            provide_context(server_state);
+            let suspense_state = SuspenseStream {
+                futures: futures.clone(),
+            };
+
+            provide_context(suspense_state);
 
+            let view = view();
+            ssr_node::render_recursive_view(&view, &mut body);
+            body.push_str(&server_state.render_pending());
+            head = head_tags.render_to_string();
+
+            // Keep a buffer of all futures being polled. This is to avoid holding onto a lock
+            // over a wait point causing potential deadlocks.
+            let mut pending_futures = futures.take();
+            sycamore_futures::spawn_local_scoped(async move {
+                while let Some(fragment) = pending_futures.next().await {
+                    // Send the state that was provided while resolving the fragment first so
+                    // that it is available when the fragment is hydrated.
+                    let mut html = server_state.render_pending();
+                    html.push_str(&render_suspense_fragment(fragment));
+                    tx.send(html).await.unwrap();
+
+                    // There can be more futures now. Add them to pending_futures.
+                    pending_futures.extend(futures.take());
+                }
             });
         });
+    });
 
-        // ```js
-        // function __sycamore_suspense(key) {
-        //   let start = document.querySelector(`suspense-start[data-key="${key}"]`)
-        //   let end = document.querySelector(`suspense-end[data-key="${key}"]`)
-        //   let template = document.getElementById(`sycamore-suspense-${key}`)
-        //   start.parentNode.insertBefore(template.content, start)
-        //   while (start.nextSibling != end) {
-        //     start.parentNode.removeChild(start.nextSibling)
-        //   }
-        // }
-        // ```
-        static SUSPENSE_REPLACE_SCRIPT: &str = r#"<script>function __sycamore_suspense(e){let s=document.querySelector(`suspense-start[data-key="${e}"]`),n=document.querySelector(`suspense-end[data-key="${e}"]`),r=document.getElementById(`sycamore-suspense-${e}`);for(s.parentNode.insertBefore(r.content,s);s.nextSibling!=n;)s.parentNode.removeChild(s.nextSibling);}</script>"#;
-        async_stream::stream! {
-            let mut initial = String::new();
-            initial.push_str("<!doctype html>");
-            initial.push_str(&buf);
-            initial.push_str(SUSPENSE_REPLACE_SCRIPT);
-            yield initial;
-
-            while let Some(fragment) = rx.next().await {
-                yield render_suspense_fragment(fragment);
-            }
-        }
-    }
+    (RenderedView { head, body }, rx)
 }
 
 #[cfg_ssr]
@@ -334,6 +682,37 @@ mod tests {
         assert!(sender.send(()).is_err(), "receiver should be dropped");
     }
 
+    #[test]
+    fn render_to_string_with_head_returns_head_tags() {
+        let res = render_to_string_with_head(|| {
+            view! {
+                Title(text="Hello")
+                p { "Hello, world!" }
+            }
+        });
+        expect![[r#"<title data-sycamore-head="title">Hello</title>"#]].assert_eq(&res.head);
+        expect![[r#"<p data-hk="0.0">Hello, world!</p>"#]].assert_eq(&res.body);
+    }
+
+    #[test]
+    fn render_to_string_stream_with_head_returns_head_tags() {
+        use futures::StreamExt;
+        use sycamore_futures::TestExecutor;
+
+        let executor = TestExecutor::new();
+        let (head, stream) = executor.enter(|| {
+            render_to_string_stream_with_head(|| {
+                view! {
+                    Title(text="Hello")
+                    p { "Hello, world!" }
+                }
+            })
+        });
+        let fragments = executor.run(stream.collect::<Vec<_>>()).unwrap();
+        expect![[r#"<title data-sycamore-head="title">Hello</title>"#]].assert_eq(&head);
+        assert!(fragments[0].contains("Hello, world!"));
+    }
+
     #[tokio::test]
     async fn render_to_string_await_suspense_works() {
         let (sender, receiver) = oneshot::channel();
@@ -349,4 +728,340 @@ mod tests {
         ]];
         expect.assert_eq(&res);
     }
+
+    #[tokio::test]
+    async fn render_to_string_await_suspense_with_timeout_renders_fallback() {
+        use std::time::Duration;
+
+        #[component]
+        async fn Never() -> View {
+            futures::future::pending::<()>().await;
+            view! {}
+        }
+
+        let output = render_to_string_await_suspense_with_timeout(
+            || {
+                view! {
+                    Suspense(fallback=|| view! { p { "fallback" } }) {
+                        Never {}
+                    }
+                }
+            },
+            Duration::from_millis(10),
+        )
+        .await;
+
+        assert_eq!(output.timed_out, [NonZeroU32::new(1).unwrap()]);
+        let expect = expect![[
+            r#"<suspense-start data-key="1" data-timeout="" data-hk="0.0"></suspense-start><no-ssr data-hk="0.1"></no-ssr><!--/--><p>fallback</p><!--/--><suspense-end data-key="1"></suspense-end>"#
+        ]];
+        expect.assert_eq(&output.html);
+    }
+
+    #[tokio::test]
+    async fn render_to_string_await_suspense_with_timeout_stops_transition() {
+        use std::time::Duration;
+
+        #[component]
+        async fn Never() -> View {
+            futures::future::pending::<()>().await;
+            view! {}
+        }
+
+        let output = tokio::time::timeout(
+            Duration::from_secs(3),
+            render_to_string_await_suspense_with_timeout(
+                || {
+                    view! {
+                        Transition(fallback=|| "fallback".into()) {
+                            Never {}
+                        }
+                    }
+                },
+                Duration::from_millis(10),
+            ),
+        )
+        .await
+        .expect("render should stop at the deadline");
+
+        assert_eq!(output.timed_out, [NonZeroU32::new(1).unwrap()]);
+        assert!(output.html.contains("fallback"), "{}", output.html);
+    }
+
+    #[tokio::test]
+    async fn transition_timeout_prop() {
+        use std::time::Duration;
+
+        #[component]
+        async fn Never() -> View {
+            futures::future::pending::<()>().await;
+            view! {}
+        }
+
+        let output = render_to_string_await_suspense_with_timeout(
+            || {
+                view! {
+                    Transition(fallback=|| "fallback".into(), timeout=Duration::from_millis(10)) {
+                        Never {}
+                    }
+                }
+            },
+            Duration::from_secs(60),
+        )
+        .await;
+
+        assert_eq!(output.timed_out, [NonZeroU32::new(1).unwrap()]);
+    }
+
+    #[tokio::test]
+    async fn suspense_timeout_prop() {
+        use std::time::Duration;
+
+        #[component]
+        async fn Never() -> View {
+            futures::future::pending::<()>().await;
+            view! {}
+        }
+
+        let (sender, receiver) = oneshot::channel();
+        sender.send(()).unwrap();
+        let output = render_to_string_await_suspense_with_timeout(
+            move || {
+                view! {
+                    Suspense(fallback=|| "slow fallback".into(), timeout=Duration::from_millis(10)) {
+                        Never {}
+                    }
+                    App(receiver=receiver)
+                }
+            },
+            Duration::from_secs(60),
+        )
+        .await;
+
+        assert_eq!(output.timed_out, [NonZeroU32::new(1).unwrap()]);
+        assert!(output.html.contains("slow fallback"));
+        assert!(output.html.contains("Hello, async!"));
+    }
+
+    #[tokio::test]
+    async fn await_renders_future_output() {
+        let res = render_to_string_await_suspense(|| {
+            view! {
+                Suspense {
+                    Await(future=async { 123 }, then=|value| view! { p { (value) } })
+                }
+                TryAwait(
+                    future=async { Err::<i32, _>("oops") },
+                    then=|value| view! { (value) },
+                    error=|err| view! { span { (err) } },
+                    fallback=|| "fallback".into(),
+                )
+            }
+        })
+        .await;
+        let expect = expect![[
+            r#"<suspense-start data-key="1" data-hk="0.0"></suspense-start><no-ssr data-hk="0.1"></no-ssr><!--/--><!--/--><p data-hk="1.0">123</p><!--/--><!--/--><suspense-start data-key="2" data-hk="0.2"></suspense-start><no-ssr data-hk="0.3"></no-ssr><!--/--><!--/--><span data-hk="2.0">oops</span><!--/--><!--/-->"#
+        ]];
+        expect.assert_eq(&res);
+    }
+
+    #[test]
+    fn await_streams_future_output() {
+        use std::time::Duration;
+
+        use futures::StreamExt;
+        use sycamore_futures::{sleep, TestExecutor};
+
+        let executor = TestExecutor::new();
+        let stream = executor.enter(|| {
+            render_to_string_stream(|| {
+                view! {
+                    Suspense(fallback=|| "fallback".into()) {
+                        Await(
+                            future=async {
+                                sleep(Duration::from_secs(1)).await;
+                                123
+                            },
+                            then=|value| view! { p { (value) } },
+                        )
+                    }
+                    TryAwait(
+                        future=async {
+                            sleep(Duration::from_secs(1)).await;
+                            Err::<i32, _>("oops")
+                        },
+                        then=|value| view! { (value) },
+                        error=|err| view! { span { (err) } },
+                        fallback=|| "fallback".into(),
+                    )
+                }
+            })
+        });
+        executor.advance_time(Duration::from_secs(1));
+        let fragments = executor.run(stream.collect::<Vec<_>>()).unwrap();
+        let expect = expect![[
+            r#"<!doctype html><no-ssr data-hk="0.1"></no-ssr><suspense-start data-key="1" data-hk="0.0"></suspense-start><!--/--><!--/-->fallback<!--/--><!--/--><suspense-end data-key="1"></suspense-end><no-ssr data-hk="0.3"></no-ssr><suspense-start data-key="2" data-hk="0.2"></suspense-start><!--/--><!--/-->fallback<!--/--><!--/--><suspense-end data-key="2"></suspense-end><script>function __sycamore_suspense(e){let s=document.querySelector(`suspense-start[data-key="${e}"]`),n=document.querySelector(`suspense-end[data-key="${e}"]`),r=document.getElementById(`sycamore-suspense-${e}`);for(s.parentNode.insertBefore(r.content,s);s.nextSibling!=n;)s.parentNode.removeChild(s.nextSibling);}</script><template id="sycamore-suspense-1"><!--/--><!--/--><p data-hk="1.0">123</p><!--/--><!--/--></template><script>__sycamore_suspense(1)</script><template id="sycamore-suspense-2"><!--/--><!--/--><span data-hk="2.0">oops</span><!--/--><!--/--></template><script>__sycamore_suspense(2)</script>"#
+        ]];
+        expect.assert_eq(&fragments.concat());
+    }
+
+    #[test]
+    fn await_renders_own_fallback() {
+        let res = render_to_string(|| {
+            view! {
+                Await(
+                    future=futures::future::pending::<()>(),
+                    then=|_| view! {},
+                    fallback=|| "fallback".into(),
+                )
+            }
+        });
+        assert!(res.contains("fallback"));
+    }
+
+    #[test]
+    fn render_view_to_string_between_steps() {
+        use std::time::Duration;
+
+        use sycamore_futures::{
+            create_signal_history, sleep, use_is_loading_global, with_test_executor,
+        };
+
+        #[component]
+        async fn Delayed() -> View {
+            sleep(Duration::from_secs(1)).await;
+            view! { "Hello, delayed!" }
+        }
+
+        with_test_executor(|executor| {
+            provide_context(HydrationRegistry::new());
+            provide_context(SsrMode::Blocking);
+
+            let view = view! {
+                Suspense(fallback=|| "fallback".into()) {
+                    Delayed {}
+                }
+            };
+            let is_loading = create_signal_history(use_is_loading_global);
+
+            executor.run_until_stalled();
+            assert_eq!(is_loading.take(), [true]);
+            assert!(!render_view_to_string(&view).contains("Hello, delayed!"));
+
+            executor.advance_time(Duration::from_secs(1));
+            assert_eq!(is_loading.take(), [false]);
+            assert!(render_view_to_string(&view).contains("Hello, delayed!"));
+        });
+    }
+
+    #[test]
+    fn suspense_list_streams_fragments_in_order() {
+        use std::time::Duration;
+
+        use futures::StreamExt;
+        use sycamore_futures::{sleep, TestExecutor};
+
+        #[component(inline_props)]
+        async fn Delayed(ms: u64) -> View {
+            sleep(Duration::from_millis(ms)).await;
+            view! { (ms.to_string()) }
+        }
+
+        let render = |reveal_order| {
+            let executor = TestExecutor::new();
+            let stream = executor.enter(|| {
+                render_to_string_stream(move || {
+                    view! {
+                        SuspenseList(reveal_order=reveal_order) {
+                            Suspense { Delayed(ms=200) }
+                            Suspense { Delayed(ms=100) }
+                        }
+                    }
+                })
+            });
+            executor.advance_time(Duration::from_millis(200));
+            let fragments = executor.run(stream.collect::<Vec<_>>()).unwrap();
+            fragments[1..]
+                .iter()
+                .map(|fragment| {
+                    let rest = fragment
+                        .strip_prefix("<template id=\"sycamore-suspense-")
+                        .unwrap();
+                    let (key, _) = rest.split_once('"').unwrap();
+                    key.to_string()
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(render(RevealOrder::Forwards), ["1", "2"]);
+        assert_eq!(render(RevealOrder::Backwards), ["2", "1"]);
+        assert_eq!(render(RevealOrder::Together), ["1", "2"]);
+    }
+
+    #[component]
+    fn Page() -> View {
+        view! {
+            ul {
+                Indexed(list=(0..10).collect::<Vec<_>>(), view=|i| view! { li { "Item " (i) } })
+            }
+        }
+    }
+
+    #[test]
+    fn render_to_writer_matches_render_to_string() {
+        let mut buf = Vec::new();
+        render_to_writer(Page, &mut buf).unwrap();
+        assert_eq!(String::from_utf8(buf).unwrap(), render_to_string(Page));
+
+        let mut buf = String::new();
+        render_to_fmt_writer(Page, &mut buf).unwrap();
+        assert_eq!(buf, render_to_string(Page));
+    }
+
+    #[test]
+    fn render_to_writer_chunked_writes_chunks() {
+        /// Records every chunk that is written.
+        #[derive(Default)]
+        struct Chunks(Vec<Vec<u8>>);
+
+        impl std::io::Write for Chunks {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.0.push(buf.to_vec());
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let mut chunks = Chunks::default();
+        render_to_writer_chunked(Page, &mut chunks, 64).unwrap();
+        let (last, rest) = chunks.0.split_last().unwrap();
+        assert!(!rest.is_empty());
+        assert!(rest.iter().all(|chunk| chunk.len() >= 64));
+        assert!(!last.is_empty());
+        assert_eq!(
+            String::from_utf8(chunks.0.concat()).unwrap(),
+            render_to_string(Page)
+        );
+    }
+
+    #[test]
+    fn render_to_writer_returns_io_error() {
+        struct Failing;
+
+        impl std::io::Write for Failing {
+            fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+                Err(std::io::ErrorKind::BrokenPipe.into())
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let err = render_to_writer(Page, &mut Failing).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::BrokenPipe);
+    }
 }
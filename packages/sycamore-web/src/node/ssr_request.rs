@@ -0,0 +1,331 @@
+//! Accessing the HTTP request and controlling the HTTP response from components in SSR mode.
+
+use std::cell::RefCell;
+
+use super::*;
+
+/// The HTTP request that is being rendered on the server.
+///
+/// Render a view for the request using [`render_to_string`](Self::render_to_string) or one of the
+/// other render methods. The components of the view can then read the request using
+/// [`use_ssr_request`] and control the response using [`use_ssr_response`]. For the other render
+/// functions, wrap the view using [`wrap_view`](Self::wrap_view).
+///
+/// # Example
+/// ```
+/// # use sycamore::prelude::*;
+/// # use sycamore::web::{use_ssr_response, SsrRequest};
+/// #[component]
+/// fn NotFound() -> View {
+///     // This is `None` on the client.
+///     if let Some(response) = use_ssr_response() {
+///         response.set_status(404);
+///     }
+///     view! { h1 { "Page not found" } }
+/// }
+///
+/// let request = SsrRequest::new("/missing").header("Cookie", "theme=dark");
+/// let (html, response) = request.render_to_string(NotFound);
+/// assert_eq!(response.status, 404);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SsrRequest {
+    url: String,
+    headers: Vec<(String, String)>,
+}
+
+impl SsrRequest {
+    /// Creates a request for `url`, which usually is the path and query of the request.
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            headers: Vec::new(),
+        }
+    }
+
+    /// Adds a header to the request.
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// The URL of the request.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// All the headers of the request, in the order they were added.
+    pub fn headers(&self) -> &[(String, String)] {
+        &self.headers
+    }
+
+    /// Returns the value of the first header with the given `name`. Header names are compared
+    /// case-insensitively.
+    pub fn get_header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns the value of the cookie with the given `name` from the `Cookie` headers.
+    pub fn cookie(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .filter(|(n, _)| n.eq_ignore_ascii_case("cookie"))
+            .flat_map(|(_, value)| value.split(';'))
+            .filter_map(|cookie| cookie.trim().split_once('='))
+            .find(|(n, _)| *n == name)
+            .map(|(_, value)| value)
+    }
+
+    /// Wraps `view` so that its components can read this request using [`use_ssr_request`] and
+    /// control the response using [`use_ssr_response`].
+    ///
+    /// The returned view function can be passed to any of the SSR render functions, e.g.
+    /// [`render_document`] or [`render_to_writer`], that do not have an equivalent method on
+    /// `SsrRequest`. Once rendering is done, the returned handle holds the response that was set
+    /// by the components.
+    ///
+    /// The handle shares the response with the components, which run on the current thread, so it
+    /// is not `Send`. Call [`SsrResponseHandle::get`] to get an [`SsrResponse`] that can be sent
+    /// to other threads, e.g. when building the response in a multi-threaded web server.
+    pub fn wrap_view(
+        self,
+        view: impl FnOnce() -> View,
+    ) -> (impl FnOnce() -> View, SsrResponseHandle) {
+        let response = SsrResponseHandle::default();
+        let view = {
+            let response = response.clone();
+            move || {
+                provide_context(Rc::new(self));
+                provide_context(response);
+                view()
+            }
+        };
+        (view, response)
+    }
+
+    /// Renders `view` for this request using [`render_to_string`](crate::render_to_string).
+    ///
+    /// Returns the HTML and the response that was set by the components.
+    pub fn render_to_string(self, view: impl FnOnce() -> View) -> (String, SsrResponse) {
+        let (view, response) = self.wrap_view(view);
+        let html = crate::render_to_string(view);
+        (html, response.get())
+    }
+
+    /// Renders `view` for this request using [`render_to_string_await_suspense`].
+    ///
+    /// Returns the HTML and the response that was set by the components.
+    #[cfg(feature = "suspense")]
+    pub async fn render_to_string_await_suspense(
+        self,
+        view: impl FnOnce() -> View,
+    ) -> (String, SsrResponse) {
+        let (view, response) = self.wrap_view(view);
+        let html = render_to_string_await_suspense(view).await;
+        (html, response.get())
+    }
+
+    /// Renders `view` for this request using [`render_to_string_stream`].
+    ///
+    /// Returns the response that was set by the components while rendering the initial HTML and
+    /// the stream of HTML. Changes to the response after that cannot be sent anymore, since the
+    /// status and headers are sent before the body.
+    #[cfg(feature = "suspense")]
+    pub fn render_to_string_stream(
+        self,
+        view: impl FnOnce() -> View,
+    ) -> (SsrResponse, impl futures::Stream<Item = String> + Send) {
+        let (view, response) = self.wrap_view(view);
+        let stream = render_to_string_stream(view);
+        (response.get(), stream)
+    }
+}
+
+/// The HTTP response that is accumulated by the components while rendering on the server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SsrResponse {
+    /// The status code of the response. This is `200` unless set otherwise.
+    pub status: u16,
+    /// The headers that should be added to the response.
+    pub headers: Vec<(String, String)>,
+    /// The URL to redirect to, if any. A `Location` header with this URL is also in `headers`.
+    pub redirect: Option<String>,
+}
+
+impl Default for SsrResponse {
+    fn default() -> Self {
+        Self {
+            status: 200,
+            headers: Vec::new(),
+            redirect: None,
+        }
+    }
+}
+
+/// A handle to the [`SsrResponse`] that is being rendered. Obtained using [`use_ssr_response`] in
+/// components or from [`SsrRequest::wrap_view`].
+#[derive(Debug, Clone, Default)]
+pub struct SsrResponseHandle {
+    response: Rc<RefCell<SsrResponse>>,
+}
+
+impl SsrResponseHandle {
+    /// Sets the status code of the response, e.g. `404` for a page that was not found.
+    pub fn set_status(&self, status: u16) {
+        self.response.borrow_mut().status = status;
+    }
+
+    /// Sets the header `name` to `value`, replacing all the existing headers with the same name.
+    pub fn set_header(&self, name: impl Into<String>, value: impl Into<String>) {
+        let name = name.into();
+        let mut response = self.response.borrow_mut();
+        response
+            .headers
+            .retain(|(n, _)| !n.eq_ignore_ascii_case(&name));
+        response.headers.push((name, value.into()));
+    }
+
+    /// Adds a header to the response, e.g. a `Set-Cookie` header.
+    pub fn append_header(&self, name: impl Into<String>, value: impl Into<String>) {
+        self.response
+            .borrow_mut()
+            .headers
+            .push((name.into(), value.into()));
+    }
+
+    /// Redirects to `url` with a `302 Found` status.
+    pub fn redirect(&self, url: impl Into<String>) {
+        let url = url.into();
+        self.set_status(302);
+        self.set_header("Location", url.clone());
+        self.response.borrow_mut().redirect = Some(url);
+    }
+
+    /// Returns the response as it is right now.
+    ///
+    /// When streaming, only the changes that are made before the initial HTML is rendered can
+    /// still be sent to the client, since the status and headers are sent before the body.
+    pub fn get(&self) -> SsrResponse {
+        self.response.borrow().clone()
+    }
+}
+
+/// Returns the HTTP request that is being rendered, if it was provided using
+/// [`SsrRequest::wrap_view`].
+///
+/// This always returns `None` on the client.
+pub fn use_ssr_request() -> Option<Rc<SsrRequest>> {
+    try_use_context()
+}
+
+/// Returns a handle for controlling the HTTP response, if the view was wrapped using
+/// [`SsrRequest::wrap_view`].
+///
+/// This always returns `None` on the client, so changes to the response are simply skipped
+/// there.
+pub fn use_ssr_response() -> Option<SsrResponseHandle> {
+    try_use_context()
+}
+
+#[cfg(test)]
+#[cfg_ssr]
+mod tests {
+    use super::*;
+
+    #[component]
+    fn NotFound() -> View {
+        let response = use_ssr_response().unwrap();
+        response.set_status(404);
+        response.append_header("Set-Cookie", "a=1");
+        response.append_header("Set-Cookie", "b=2");
+        response.set_header("Cache-Control", "no-cache");
+        response.set_header("cache-control", "no-store");
+        view! { "Not found" }
+    }
+
+    #[test]
+    fn read_request() {
+        let request = SsrRequest::new("/page?q=1")
+            .header("Accept", "text/html")
+            .header("Cookie", "theme=dark; lang=en")
+            .header("cookie", "session=abc");
+        assert_eq!(request.get_header("accept"), Some("text/html"));
+        assert_eq!(request.cookie("lang"), Some("en"));
+        assert_eq!(request.cookie("session"), Some("abc"));
+        assert_eq!(request.cookie("missing"), None);
+
+        let (view, _) =
+            request.wrap_view(|| View::from(use_ssr_request().unwrap().url().to_string()));
+        assert_eq!(render_to_string(view), "/page?q=1");
+    }
+
+    #[test]
+    fn response_is_returned_after_render() {
+        let (view, response) = SsrRequest::new("/missing").wrap_view(NotFound);
+        assert_eq!(render_to_string(view), "Not found");
+        assert_eq!(
+            response.get(),
+            SsrResponse {
+                status: 404,
+                headers: vec![
+                    ("Set-Cookie".to_string(), "a=1".to_string()),
+                    ("Set-Cookie".to_string(), "b=2".to_string()),
+                    ("cache-control".to_string(), "no-store".to_string()),
+                ],
+                redirect: None,
+            }
+        );
+    }
+
+    #[test]
+    fn render_to_string_returns_response() {
+        let (html, response) = SsrRequest::new("/missing").render_to_string(NotFound);
+        assert_eq!(html, "Not found");
+        assert_eq!(response.status, 404);
+    }
+
+    #[cfg(feature = "suspense")]
+    #[tokio::test]
+    async fn render_to_string_await_suspense_returns_response() {
+        #[component]
+        async fn AsyncNotFound() -> View {
+            use_ssr_response().unwrap().set_status(404);
+            view! { "Not found" }
+        }
+
+        let (html, response) = SsrRequest::new("/missing")
+            .render_to_string_await_suspense(|| view! { Suspense { AsyncNotFound {} } })
+            .await;
+        assert!(html.contains("Not found"));
+        assert_eq!(response.status, 404);
+    }
+
+    #[test]
+    fn redirect() {
+        let (view, response) = SsrRequest::new("/old").wrap_view(|| {
+            use_ssr_response().unwrap().redirect("/new");
+            View::default()
+        });
+        let _ = render_to_string(view);
+        let response = response.get();
+        assert_eq!(response.status, 302);
+        assert_eq!(response.redirect.as_deref(), Some("/new"));
+        assert_eq!(
+            response.headers,
+            [("Location".to_string(), "/new".to_string())]
+        );
+    }
+
+    #[test]
+    fn no_request_without_wrap_view() {
+        let html = render_to_string(|| {
+            assert!(use_ssr_request().is_none());
+            assert!(use_ssr_response().is_none());
+            View::default()
+        });
+        assert_eq!(html, "");
+    }
+}
@@ -11,6 +11,12 @@ pub fn Portal<'a, T: Into<View> + Default>(selector: &'a str, children: T) -> Vi
             panic!("element matching selector `{selector}` not found");
         };
 
+        // Events do not bubble from the portal to the root the view was rendered into, so the
+        // portal target becomes the root for delegated events instead.
+        is_not_ssr! {
+            DelegationRoot::provide(&parent);
+        }
+
         let start = HtmlNode::create_marker_node();
         let start_node = start.as_web_sys().clone();
         let end = HtmlNode::create_marker_node();
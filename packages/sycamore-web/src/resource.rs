@@ -2,6 +2,8 @@
 
 use std::future::Future;
 use std::ops::Deref;
+use std::panic::Location;
+use std::time::Duration;
 
 use futures::future::{FutureExt, LocalBoxFuture};
 use sycamore_futures::{SuspenseScope, SuspenseTaskGuard};
@@ -9,7 +11,6 @@ use sycamore_futures::{SuspenseScope, SuspenseTaskGuard};
 use crate::*;
 
 /// Represents a asynchronous resource.
-#[derive(Clone, Copy)]
 pub struct Resource<T: 'static> {
     /// The current value of the resource.
     ///
@@ -25,10 +26,26 @@ pub struct Resource<T: 'static> {
     scopes: Signal<Vec<SuspenseScope>>,
     /// A list of suspense guards that are currently active.
     guards: Signal<Vec<SuspenseTaskGuard>>,
+    /// Signal that is used to manually trigger a refetch.
+    trigger: Signal<()>,
+    /// Whether automatic refreshes should be skipped while the document is hidden.
+    pause_when_hidden: Signal<bool>,
+    /// Whether an automatic refresh was skipped because the document was hidden.
+    missed_refresh: Signal<bool>,
+    /// Where the resource was created. Used for suspense task introspection.
+    location: &'static Location<'static>,
 }
 
+impl<T> Clone for Resource<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<T> Copy for Resource<T> {}
+
 impl<T: 'static> Resource<T> {
     /// Create a new resource. By itself, this doesn't do anything.
+    #[track_caller]
     fn new<F, Fut>(mut refetch: F) -> Self
     where
         F: FnMut() -> Fut + 'static,
@@ -40,22 +57,40 @@ impl<T: 'static> Resource<T> {
             refetch: create_signal(Box::new(move || refetch().boxed_local())),
             scopes: create_signal(Vec::new()),
             guards: create_signal(Vec::new()),
+            trigger: create_signal(()),
+            pause_when_hidden: create_signal(false),
+            missed_refresh: create_signal(false),
+            location: Location::caller(),
         }
     }
 
+    /// Creates a suspense guard for `scope`, or the current suspense scope if `None`, that is
+    /// labeled with this resource.
+    fn create_guard(&self, scope: Option<SuspenseScope>) -> SuspenseTaskGuard {
+        let guard = match scope {
+            Some(scope) => SuspenseTaskGuard::from_scope(scope),
+            None => SuspenseTaskGuard::new(),
+        };
+        guard
+            .with_label(format!("Resource<{}>", std::any::type_name::<T>()))
+            .with_location(self.location)
+    }
+
     /// Attach handlers to always call the refetch function to get the latest value.
     fn always_refetch(self) -> Self {
         create_effect(move || {
+            self.trigger.track();
             self.is_loading.set(true);
             // Take all the scopes and create a new guard.
             for scope in self.scopes.take() {
-                let guard = SuspenseTaskGuard::from_scope(scope);
+                let guard = self.create_guard(Some(scope));
                 self.guards.update(|guards| guards.push(guard));
             }
 
             let fut = self.refetch.update_silent(|f| f());
 
-            sycamore_futures::create_suspense_task(async move {
+            let guard = self.create_guard(None);
+            sycamore_futures::spawn_local_scoped(async move {
                 let value = fut.await;
                 batch(move || {
                     self.value.set(Some(value));
@@ -63,6 +98,7 @@ impl<T: 'static> Resource<T> {
                     // Now, drop all the guards to resolve suspense.
                     self.guards.update(|guards| guards.clear());
                 });
+                drop(guard);
             });
         });
 
@@ -73,6 +109,89 @@ impl<T: 'static> Resource<T> {
     pub fn is_loading(&self) -> bool {
         self.is_loading.get()
     }
+
+    /// Fetch the resource again, even if none of its dependencies have changed.
+    ///
+    /// The resource keeps its previous value until the new value is loaded.
+    pub fn refetch(&self) {
+        self.trigger.set(());
+    }
+
+    /// Automatically refetch the resource every `interval`.
+    ///
+    /// A refresh is skipped if the previous fetch has not completed yet. The timer is stopped
+    /// when the current scope is destroyed. This does nothing in SSR mode.
+    pub fn refetch_interval(self, interval: Duration) -> Self {
+        if is_not_ssr!() {
+            sycamore_futures::spawn_local_scoped(async move {
+                loop {
+                    sycamore_futures::sleep(interval).await;
+                    if self.is_loading.get_untracked() {
+                        continue;
+                    }
+                    if self.pause_when_hidden.get_untracked() && document().hidden() {
+                        self.missed_refresh.set(true);
+                        continue;
+                    }
+                    self.refetch();
+                }
+            });
+        }
+        self
+    }
+
+    /// Automatically refetch the resource whenever the window regains focus.
+    ///
+    /// This does nothing in SSR mode.
+    pub fn refetch_on_focus(self) -> Self {
+        if is_not_ssr!() {
+            on_window_event("focus", move || self.refetch());
+        }
+        self
+    }
+
+    /// Automatically refetch the resource whenever the browser goes back online after losing its
+    /// network connection.
+    ///
+    /// This does nothing in SSR mode.
+    pub fn refetch_on_reconnect(self) -> Self {
+        if is_not_ssr!() {
+            on_window_event("online", move || self.refetch());
+        }
+        self
+    }
+
+    /// Skip automatic refreshes from [`Resource::refetch_interval`] while the document is hidden,
+    /// e.g. when the tab is in the background. If a refresh was skipped, the resource is refetched
+    /// as soon as the document becomes visible again.
+    ///
+    /// This does nothing in SSR mode.
+    pub fn pause_when_hidden(self) -> Self {
+        self.pause_when_hidden.set(true);
+        if is_not_ssr!() {
+            // `visibilitychange` is fired on the document, but bubbles up to the window.
+            on_window_event("visibilitychange", move || {
+                if !document().hidden() && self.missed_refresh.get_untracked() {
+                    self.missed_refresh.set(false);
+                    self.refetch();
+                }
+            });
+        }
+        self
+    }
+}
+
+/// Adds an event listener to the window that is removed when the current scope is destroyed.
+fn on_window_event(name: &'static str, f: impl FnMut() + 'static) {
+    let cb = Closure::<dyn FnMut()>::new(f);
+    window()
+        .add_event_listener_with_callback(name, cb.as_ref().unchecked_ref())
+        .unwrap();
+    on_cleanup(move || {
+        window()
+            .remove_event_listener_with_callback(name, cb.as_ref().unchecked_ref())
+            .unwrap();
+    });
 }
 
 /// Hijack deref so that we can track where the resource is being accessed.
@@ -83,7 +202,7 @@ impl<T: 'static> Deref for Resource<T> {
         // If we are already loading, add a new suspense guard. Otherwise, register the scope so
         // that we can create a new guard when loading.
         if self.is_loading.get() {
-            let guard = SuspenseTaskGuard::new();
+            let guard = self.create_guard(None);
             self.guards.update(|guards| guards.push(guard));
         } else if let Some(scope) = try_use_context::<SuspenseScope>() {
             self.scopes.update(|scopes| scopes.push(scope));
@@ -98,6 +217,7 @@ impl<T: 'static> Deref for Resource<T> {
 /// If the resource has any dependencies, it is recommended to use [`on`] to make them explicit.
 /// This will ensure that the dependencies are tracked since reactive variables inside async
 /// contexts are not tracked automatically.
+#[track_caller]
 pub fn create_isomorphic_resource<F, Fut, T>(f: F) -> Resource<T>
 where
     F: FnMut() -> Fut + 'static,
@@ -114,6 +234,7 @@ where
 /// If the resource has any dependencies, it is recommended to use [`on`] to make them explicit.
 /// This will ensure that the dependencies are tracked since reactive variables inside async
 /// contexts are not tracked automatically.
+#[track_caller]
 pub fn create_client_resource<F, Fut, T>(f: F) -> Resource<T>
 where
     F: FnMut() -> Fut + 'static,
@@ -181,4 +302,52 @@ mod tests {
         })
         .await;
     }
+
+    #[test]
+    fn refetch_fetches_again() {
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        use sycamore_futures::with_test_executor;
+
+        with_test_executor(|executor| {
+            let fetches = Rc::new(Cell::new(0));
+            let resource = {
+                let fetches = Rc::clone(&fetches);
+                create_isomorphic_resource(move || {
+                    fetches.set(fetches.get() + 1);
+                    let value = fetches.get();
+                    async move { value }
+                })
+            };
+            executor.run_until_stalled();
+            assert_eq!(resource.get(), Some(1));
+
+            resource.refetch();
+            assert!(resource.is_loading());
+            assert_eq!(resource.get(), Some(1));
+            executor.run_until_stalled();
+            assert_eq!(resource.get(), Some(2));
+        });
+    }
+
+    #[test]
+    fn automatic_refresh_is_disabled_in_ssr() {
+        use sycamore_futures::with_test_executor;
+
+        with_test_executor(|executor| {
+            let resource = create_isomorphic_resource(|| async { 123 })
+                .refetch_interval(Duration::from_secs(1))
+                .refetch_on_focus()
+                .refetch_on_reconnect()
+                .pause_when_hidden();
+            executor.run_until_stalled();
+            assert_eq!(resource.get(), Some(123));
+            assert_eq!(
+                executor.pending_tasks(),
+                0,
+                "no interval timer should be spawned"
+            );
+        });
+    }
 }
@@ -0,0 +1,243 @@
+//! Passing state from the server to the client.
+
+#[cfg(feature = "server-state")]
+use serde::de::DeserializeOwned;
+#[cfg(feature = "server-state")]
+use serde::Serialize;
+
+use crate::*;
+
+/// The attribute of the `<script>` elements that hold the server state. The value of the
+/// attribute is the key of the state.
+#[cfg_attr(not(feature = "server-state"), allow(dead_code))]
+const SERVER_STATE_ATTR: &str = "data-sycamore-state";
+
+/// The state that is passed to the client using [`provide_server_state`]. The values are stored
+/// as JSON.
+///
+/// This is provided by the SSR render functions.
+#[cfg_ssr]
+#[derive(Clone, Copy)]
+pub(crate) struct ServerState {
+    values: Signal<Vec<(String, String)>>,
+    /// The number of values that have already been rendered.
+    rendered: Signal<usize>,
+}
+
+#[cfg_ssr]
+impl ServerState {
+    pub fn new() -> Self {
+        Self {
+            values: create_signal(Vec::new()),
+            rendered: create_signal(0),
+        }
+    }
+
+    /// Renders the values that have not been rendered yet into `<script>` elements.
+    ///
+    /// This is called once the initial HTML is rendered and, when streaming, before every
+    /// suspense fragment, so that the state is available on the client before the content that
+    /// uses it is hydrated.
+    pub fn render_pending(self) -> String {
+        let mut buf = String::new();
+        let rendered = self.rendered.get();
+        let len = self.values.with(|values| {
+            for (key, json) in &values[rendered..] {
+                buf.push_str("<script type=\"application/json\" ");
+                buf.push_str(SERVER_STATE_ATTR);
+                buf.push_str("=\"");
+                html_escape::encode_double_quoted_attribute_to_string(key, &mut buf);
+                buf.push_str("\">");
+                // Make sure that the state cannot close the script element. `<\/` is equivalent
+                // to `</` in JSON strings.
+                buf.push_str(&json.replace("</", "<\\/"));
+                buf.push_str("</script>");
+            }
+            values.len()
+        });
+        self.rendered.set(len);
+        buf
+    }
+
+    /// Adds a value that has already been serialized to JSON.
+    pub fn push(self, key: String, json: String) {
+        self.values.update(|values| values.push((key, json)));
+    }
+}
+
+/// Passes `value` from the server to the client, where it can be read using [`use_server_state`]
+/// with the same `key`.
+///
+/// In SSR mode, `value` is serialized to JSON and rendered into a
+/// `<script type="application/json">` element. When streaming, state that is provided inside of
+/// a suspense boundary is sent along with the content of the boundary. In DOM mode, this does
+/// nothing, so it is fine to call this from code that runs on both the server and the client.
+///
+/// # Example
+/// ```
+/// # use sycamore::prelude::*;
+/// # use sycamore::web::{provide_server_state, use_server_state};
+/// #[derive(serde::Serialize, serde::Deserialize)]
+/// struct Session {
+///     user: String,
+/// }
+///
+/// #[component]
+/// fn App() -> View {
+///     if is_ssr!() {
+///         provide_server_state("session", &Session { user: "alice".to_string() });
+///     }
+///     let user = use_server_state::<Session>("session")
+///         .map(|session| session.user)
+///         .unwrap_or_default();
+///     view! {
+///         p { (user) }
+///     }
+/// }
+/// ```
+///
+/// # Panics
+///
+/// Panics if `value` cannot be serialized or if this is called outside of an SSR render function
+/// in SSR mode.
+#[cfg(feature = "server-state")]
+pub fn provide_server_state<T: Serialize + ?Sized>(key: impl Into<String>, value: &T) {
+    is_not_ssr! {
+        let _ = (key, value);
+    }
+    is_ssr! {
+        let state = try_use_context::<ServerState>()
+            .expect("`provide_server_state` can only be called while rendering");
+        let json = serde_json::to_string(value).expect("could not serialize server state");
+        state.push(key.into(), json);
+    }
+}
+
+/// Reads the state with the given `key` that was passed from the server using
+/// [`provide_server_state`]. Returns `None` if there is no such state.
+///
+/// In SSR mode, this returns the last value that has been provided for `key` in the current
+/// render.
+///
+/// # Panics
+///
+/// Panics if the state cannot be deserialized into `T`.
+#[cfg(feature = "server-state")]
+pub fn use_server_state<T: DeserializeOwned>(key: &str) -> Option<T> {
+    let json = find_server_state(key)?;
+    Some(
+        serde_json::from_str(&json)
+            .unwrap_or_else(|err| panic!("could not deserialize server state `{key}`: {err}")),
+    )
+}
+
+/// Returns the JSON of the state with the given `key`.
+#[cfg(feature = "server-state")]
+fn find_server_state(key: &str) -> Option<String> {
+    is_ssr! {
+        let state = try_use_context::<ServerState>()?;
+        state.values.with(|values| {
+            values
+                .iter()
+                .rev()
+                .find(|(k, _)| k == key)
+                .map(|(_, json)| json.clone())
+        })
+    }
+    is_not_ssr! {
+        use std::cell::RefCell;
+        use std::collections::HashMap;
+
+        thread_local! {
+            /// The state that has been read from the document so far.
+            static SERVER_STATE: RefCell<HashMap<String, String>> = RefCell::default();
+        }
+
+        SERVER_STATE.with(|state| {
+            let mut state = state.borrow_mut();
+            // Read the state that has been added to the document since the last call, e.g. by a
+            // streamed suspense fragment. The attribute is removed so that every element is only
+            // read once.
+            let scripts = document()
+                .query_selector_all(&format!("script[{SERVER_STATE_ATTR}]"))
+                .unwrap();
+            for i in 0..scripts.length() {
+                let script = scripts.get(i).unwrap().unchecked_into::<web_sys::Element>();
+                let key = script.get_attribute(SERVER_STATE_ATTR).unwrap();
+                script.remove_attribute(SERVER_STATE_ATTR).unwrap();
+                state.insert(key, script.text_content().unwrap_or_default());
+            }
+            state.get(key).cloned()
+        })
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "server-state")]
+mod tests {
+    use expect_test::expect;
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Session {
+        user: String,
+    }
+
+    #[test]
+    fn render_server_state() {
+        let html = render_to_string(|| {
+            provide_server_state(
+                "session",
+                &Session {
+                    user: "</script>".to_string(),
+                },
+            );
+            let session = use_server_state::<Session>("session").unwrap();
+            assert_eq!(use_server_state::<i32>("missing"), None);
+            view! { p { (session.user) } }
+        });
+        expect![[r#"<p data-hk="0.0">&lt;/script&gt;</p><script type="application/json" data-sycamore-state="session">{"user":"<\/script>"}</script>"#]]
+            .assert_eq(&html);
+    }
+
+    #[cfg(feature = "suspense")]
+    #[test]
+    fn stream_server_state_with_fragment() {
+        use std::time::Duration;
+
+        use futures::StreamExt;
+        use sycamore_futures::{sleep, TestExecutor};
+
+        #[component]
+        async fn Delayed() -> View {
+            sleep(Duration::from_secs(1)).await;
+            provide_server_state("delayed", &1);
+            view! { "delayed" }
+        }
+
+        let executor = TestExecutor::new();
+        let stream = executor.enter(|| {
+            render_to_string_stream(|| {
+                provide_server_state("initial", &0);
+                view! {
+                    Suspense(fallback=|| "loading".into()) {
+                        Delayed {}
+                    }
+                }
+            })
+        });
+        executor.advance_time(Duration::from_secs(1));
+        let chunks = executor.run(stream.collect::<Vec<_>>()).unwrap();
+        assert!(chunks[0].contains(
+            r#"<script type="application/json" data-sycamore-state="initial">0</script>"#
+        ));
+        expect![[r#"
+            [
+                "<script type=\"application/json\" data-sycamore-state=\"delayed\">1</script><template id=\"sycamore-suspense-1\"><!--/--><!--/-->delayed<!--/--><!--/--></template><script>__sycamore_suspense(1)</script>",
+            ]
+        "#]]
+        .assert_debug_eq(&&chunks[1..]);
+    }
+}
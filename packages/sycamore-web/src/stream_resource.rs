@@ -0,0 +1,355 @@
+//! Async resources that accumulate items from a [`Stream`].
+
+use std::convert::Infallible;
+use std::ops::Deref;
+use std::panic::Location;
+
+use futures::{Stream, StreamExt};
+use sycamore_futures::{spawn_local_scoped, SuspenseScope, SuspenseTaskGuard};
+
+use crate::*;
+
+/// Determines for how long a [`StreamResource`] suspends the suspense scopes in which it is
+/// accessed.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SuspendUntil {
+    /// Suspend until the stream is completed. In streaming SSR mode, the fully resolved content is
+    /// sent to the client.
+    #[default]
+    Completion,
+    /// Suspend until the first item of the stream has arrived. In streaming SSR mode, this flushes
+    /// the partially resolved content. Items that arrive afterwards are only rendered on the
+    /// client.
+    FirstItem,
+}
+
+/// Represents an asynchronous resource that is fetched from a [`Stream`].
+///
+/// Items are accumulated into the value of the resource as they arrive. Created using
+/// [`create_stream_resource`], [`create_folded_stream_resource`] or
+/// [`create_try_stream_resource`].
+pub struct StreamResource<U: 'static, E: 'static = Infallible> {
+    /// The items that have been accumulated so far.
+    value: Signal<U>,
+    /// The error that the stream ended with, if any.
+    error: Signal<Option<E>>,
+    /// Whether the resource is currently suspending or not. See [`SuspendUntil`].
+    is_loading: Signal<bool>,
+    /// Whether the stream has completed or not.
+    is_done: Signal<bool>,
+    suspend_until: Signal<SuspendUntil>,
+    /// A list of all the suspense scopes in which the resource is accessed.
+    scopes: Signal<Vec<SuspenseScope>>,
+    /// A list of suspense guards that are currently active.
+    guards: Signal<Vec<SuspenseTaskGuard>>,
+    /// Where the resource was created. Used for suspense task introspection.
+    location: &'static Location<'static>,
+}
+
+impl<U, E> Clone for StreamResource<U, E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<U, E> Copy for StreamResource<U, E> {}
+
+impl<U: 'static, E: 'static> StreamResource<U, E> {
+    /// Create a new stream resource and start fetching it. The stream is restarted whenever the
+    /// dependencies of `f` change.
+    #[track_caller]
+    fn new<F, S, T>(
+        mut f: F,
+        mut init: impl FnMut() -> U + 'static,
+        fold: impl FnMut(&mut U, T) + 'static,
+    ) -> Self
+    where
+        F: FnMut() -> S + 'static,
+        S: Stream<Item = Result<T, E>> + 'static,
+        T: 'static,
+    {
+        let this = Self {
+            value: create_signal(untrack(&mut init)),
+            error: create_signal(None),
+            is_loading: create_signal(true),
+            is_done: create_signal(false),
+            suspend_until: create_signal(SuspendUntil::default()),
+            scopes: create_signal(Vec::new()),
+            guards: create_signal(Vec::new()),
+            location: Location::caller(),
+        };
+        let fold = create_signal(fold);
+
+        let mut first = true;
+        create_effect(move || {
+            let stream = f();
+            if !first {
+                // Reset the resource since we are restarting the stream.
+                let init = untrack(&mut init);
+                batch(|| {
+                    this.value.set(init);
+                    this.error.set(None);
+                    this.is_done.set(false);
+                    this.is_loading.set(true);
+                });
+            }
+            first = false;
+            // Take all the scopes and create a new guard.
+            for scope in this.scopes.take() {
+                let guard = this.create_guard(Some(scope));
+                this.guards.update(|guards| guards.push(guard));
+            }
+
+            // We cannot use `create_suspense_task` here because we might want to stop suspending
+            // before the stream is done.
+            let mut guard = Some(this.create_guard(None));
+            let mut release = move || {
+                this.is_loading.set(false);
+                this.guards.update(|guards| guards.clear());
+                guard.take();
+            };
+            spawn_local_scoped(async move {
+                futures::pin_mut!(stream);
+                while let Some(item) = stream.next().await {
+                    match item {
+                        Ok(item) => batch(|| {
+                            this.value
+                                .update(|value| fold.update_silent(|fold| fold(value, item)));
+                            if this.suspend_until.get_untracked() == SuspendUntil::FirstItem {
+                                release();
+                            }
+                        }),
+                        Err(err) => {
+                            this.error.set(Some(err));
+                            break;
+                        }
+                    }
+                }
+                batch(|| {
+                    this.is_done.set(true);
+                    release();
+                });
+            });
+        });
+
+        this
+    }
+
+    /// Creates a suspense guard for `scope`, or the current suspense scope if `None`, that is
+    /// labeled with this resource.
+    fn create_guard(&self, scope: Option<SuspenseScope>) -> SuspenseTaskGuard {
+        let guard = match scope {
+            Some(scope) => SuspenseTaskGuard::from_scope(scope),
+            None => SuspenseTaskGuard::new(),
+        };
+        guard
+            .with_label(format!("StreamResource<{}>", std::any::type_name::<U>()))
+            .with_location(self.location)
+    }
+
+    /// Sets for how long the resource suspends the suspense scopes in which it is accessed.
+    ///
+    /// By default, this is [`SuspendUntil::Completion`].
+    pub fn suspend_until(self, suspend_until: SuspendUntil) -> Self {
+        self.suspend_until.set(suspend_until);
+        self
+    }
+
+    /// Returns whether we are currently suspending or not. See [`SuspendUntil`].
+    pub fn is_loading(&self) -> bool {
+        self.is_loading.get()
+    }
+
+    /// Returns whether the stream has completed, either because it ran out of items or because it
+    /// returned an error.
+    pub fn is_done(&self) -> bool {
+        self.is_done.get()
+    }
+
+    /// The error that the stream ended with, if any.
+    ///
+    /// This is always `None` for streams that cannot fail.
+    pub fn error(&self) -> ReadSignal<Option<E>> {
+        *self.error
+    }
+}
+
+/// Hijack deref so that we can track where the resource is being accessed.
+impl<U: 'static, E: 'static> Deref for StreamResource<U, E> {
+    type Target = ReadSignal<U>;
+
+    fn deref(&self) -> &Self::Target {
+        // If we are already loading, add a new suspense guard. Otherwise, register the scope so
+        // that we can create a new guard when loading.
+        if self.is_loading.get() {
+            let guard = self.create_guard(None);
+            self.guards.update(|guards| guards.push(guard));
+        } else if let Some(scope) = try_use_context::<SuspenseScope>() {
+            self.scopes.update(|scopes| scopes.push(scope));
+        }
+
+        &self.value
+    }
+}
+
+/// Create a resource that appends all the items of a [`Stream`] to a [`Vec`] as they arrive. The
+/// stream is fetched on both client and server.
+///
+/// If the resource has any dependencies, it is recommended to use [`on`] to make them explicit.
+/// Whenever a dependency changes, the current stream is dropped and the resource is restarted
+/// with a new stream.
+///
+/// # Example
+/// ```
+/// # use sycamore::prelude::*;
+/// # use sycamore::web::{create_stream_resource, SuspendUntil};
+/// # #[component]
+/// # fn LogViewer() -> View {
+/// let lines = create_stream_resource(|| futures::stream::iter(["first line", "second line"]))
+///     .suspend_until(SuspendUntil::FirstItem);
+/// view! {
+///     ul {
+///         Indexed(list=*lines, view=|line| view! { li { (line) } })
+///     }
+/// }
+/// # }
+/// ```
+#[track_caller]
+pub fn create_stream_resource<F, S, T>(mut f: F) -> StreamResource<Vec<T>>
+where
+    F: FnMut() -> S + 'static,
+    S: Stream<Item = T> + 'static,
+    T: 'static,
+{
+    StreamResource::new(move || f().map(Ok), Vec::new, |vec, item| vec.push(item))
+}
+
+/// Create a resource that folds all the items of a [`Stream`] into a single value as they arrive.
+/// The stream is fetched on both client and server.
+///
+/// The value starts out as `init` and is reset to `init` whenever the stream is restarted.
+#[track_caller]
+pub fn create_folded_stream_resource<F, S, T, U>(
+    mut f: F,
+    init: U,
+    fold: impl FnMut(&mut U, T) + 'static,
+) -> StreamResource<U>
+where
+    F: FnMut() -> S + 'static,
+    S: Stream<Item = T> + 'static,
+    T: 'static,
+    U: Clone + 'static,
+{
+    StreamResource::new(move || f().map(Ok), move || init.clone(), fold)
+}
+
+/// Create a resource that appends all the items of a fallible [`Stream`] to a [`Vec`] as they
+/// arrive. The stream is fetched on both client and server.
+///
+/// The stream is stopped at the first error, which is then available from
+/// [`StreamResource::error`]. Items that arrived before the error are kept.
+#[track_caller]
+pub fn create_try_stream_resource<F, S, T, E>(f: F) -> StreamResource<Vec<T>, E>
+where
+    F: FnMut() -> S + 'static,
+    S: Stream<Item = Result<T, E>> + 'static,
+    T: 'static,
+    E: 'static,
+{
+    StreamResource::new(f, Vec::new, |vec, item| vec.push(item))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use futures::stream;
+    use sycamore_futures::{create_suspense_scope, sleep, with_test_executor};
+
+    use super::*;
+
+    /// A stream that yields `items` with a delay of 100ms before each item.
+    fn delayed<T: 'static>(items: Vec<T>) -> impl Stream<Item = T> {
+        stream::iter(items).then(|item| async move {
+            sleep(Duration::from_millis(100)).await;
+            item
+        })
+    }
+
+    #[test]
+    fn appends_items_as_they_arrive() {
+        with_test_executor(|executor| {
+            let resource = create_stream_resource(|| delayed(vec![1, 2, 3]));
+            assert_eq!(resource.get_clone(), Vec::<i32>::new());
+
+            executor.advance_time(Duration::from_millis(100));
+            assert_eq!(resource.get_clone(), [1]);
+            assert!(resource.is_loading());
+            assert!(!resource.is_done());
+
+            executor.advance_time(Duration::from_millis(200));
+            assert_eq!(resource.get_clone(), [1, 2, 3]);
+            assert!(!resource.is_loading());
+            assert!(resource.is_done());
+        });
+    }
+
+    #[test]
+    fn folds_items() {
+        with_test_executor(|executor| {
+            let resource =
+                create_folded_stream_resource(|| delayed(vec![1, 2, 3]), 0, |sum, x| *sum += x);
+            executor.advance_time(Duration::from_millis(300));
+            assert_eq!(resource.get(), 6);
+        });
+    }
+
+    #[test]
+    fn stops_at_first_error() {
+        with_test_executor(|executor| {
+            let resource = create_try_stream_resource(|| delayed(vec![Ok(1), Err("oops"), Ok(2)]));
+            executor.advance_time(Duration::from_millis(300));
+            assert_eq!(resource.get_clone(), [1]);
+            assert_eq!(resource.error().get(), Some("oops"));
+            assert!(resource.is_done());
+        });
+    }
+
+    #[test]
+    fn restarts_when_dependencies_change() {
+        with_test_executor(|executor| {
+            let page = create_signal(0);
+            let resource = create_stream_resource(on(page, move || delayed(vec![page.get(); 2])));
+            executor.advance_time(Duration::from_millis(100));
+            assert_eq!(resource.get_clone(), [0]);
+
+            page.set(1);
+            assert_eq!(resource.get_clone(), Vec::<i32>::new());
+            executor.advance_time(Duration::from_millis(200));
+            assert_eq!(resource.get_clone(), [1, 1]);
+        });
+    }
+
+    #[test]
+    fn suspend_until() {
+        with_test_executor(|executor| {
+            for (suspend_until, loading_after_first) in [
+                (SuspendUntil::Completion, true),
+                (SuspendUntil::FirstItem, false),
+            ] {
+                let (_, scope) = create_suspense_scope(|| {
+                    let resource =
+                        create_stream_resource(|| delayed(vec![1, 2])).suspend_until(suspend_until);
+                    resource.track();
+                });
+                let is_loading = scope.is_loading();
+                assert!(is_loading.get());
+
+                executor.advance_time(Duration::from_millis(100));
+                assert_eq!(is_loading.get(), loading_after_first);
+
+                executor.advance_time(Duration::from_millis(100));
+                assert!(!is_loading.get());
+            }
+        });
+    }
+}
@@ -2,9 +2,11 @@
 
 use std::future::Future;
 use std::num::NonZeroU32;
+use std::time::Duration;
 
 use sycamore_futures::{
-    create_detached_suspense_scope, create_suspense_scope, create_suspense_task,
+    create_detached_suspense_scope, create_labeled_suspense_task, create_suspense_scope,
+    create_suspense_task, SuspenseScope,
 };
 use sycamore_macro::{component, Props};
 
@@ -22,6 +24,31 @@ pub struct SuspenseProps {
     /// This is only updated in non-SSR mode.
     #[prop(default = Box::new(|_| {}), setter(transform = |f: impl FnMut(bool) + 'static| Box::new(f) as Box<dyn FnMut(bool)>))]
     set_is_loading: Box<dyn FnMut(bool) + 'static>,
+    /// Only show the fallback if loading takes longer than this. This prevents the fallback from
+    /// flashing for requests that complete quickly.
+    ///
+    /// While the fallback is delayed, the previously revealed content stays visible. On the
+    /// initial load there is no previous content, so nothing is rendered until either the
+    /// children are ready or the delay has passed.
+    ///
+    /// This is only used in non-SSR mode and is ignored when hydrating a fallback that was
+    /// rendered on the server.
+    #[prop(default)]
+    fallback_delay: Duration,
+    /// Once the fallback is shown, keep it visible for at least this long.
+    ///
+    /// This is only used in non-SSR mode.
+    #[prop(default)]
+    min_fallback_duration: Duration,
+    /// Stop waiting for the children after this long and render the fallback instead. The
+    /// boundary is then loaded on the client side, as if it were rendered in [`SsrMode::Sync`].
+    ///
+    /// This is only used in [`SsrMode::Blocking`]. In [`SsrMode::Sync`], the fallback is always
+    /// rendered on the server, and in [`SsrMode::Streaming`], the fallback is sent first and the
+    /// content is streamed whenever it is ready, so the timeout is ignored in both. A deadline for
+    /// all boundaries can be set with [`render_to_string_await_suspense_with_timeout`].
+    #[prop(default)]
+    timeout: Option<Duration>,
 }
 
 /// `Suspense` lets you wait for `async` tasks to complete before rendering the UI. This is useful
@@ -58,36 +85,96 @@ pub fn Suspense(props: SuspenseProps) -> View {
         fallback,
         children,
         mut set_is_loading,
+        fallback_delay,
+        min_fallback_duration,
+        timeout,
     } = props;
 
     is_ssr! {
         use futures::FutureExt;
 
-        let _ = &mut set_is_loading;
+        let _ = (&mut set_is_loading, fallback_delay, min_fallback_duration);
 
         let mode = use_context::<SsrMode>();
         match mode {
             // In sync mode, we don't even bother about the children and just return the fallback.
             //
             // We make sure to return a closure so that the view can be properly hydrated.
-            SsrMode::Sync => view! {
-                Show(when=true) {
-                    (fallback())
+            SsrMode::Sync => {
+                let entry = SuspenseListEntry::register(*create_signal(true), create_signal(false));
+                let show_fallback = entry.show_fallback();
+                view! {
+                    Show(when=show_fallback) {
+                        (fallback())
+                    }
+                    Show(when=false) {}
                 }
-                Show(when=false) {}
-            },
+            }
             // In blocking mode, we don't need to render the fallback. We just need to render the
             // view when it resolves.
+            //
+            // If the boundary times out, the fallback is rendered instead and the boundary is
+            // loaded on the client side.
             SsrMode::Blocking => {
+                use std::sync::{Arc, Mutex};
+
                 let key = use_suspense_key();
-                let start = view! { suspense-start(data-key=key.to_string()) };
-                let (view, _suspense_scope) = create_suspense_scope(move || HydrationRegistry::in_suspense_scope(key, move || children.call()));
-                view! {
-                    (start)
-                    NoSsr {}
+                // The start marker and the content are wrapped in dynamic nodes so that they can
+                // still be changed if the boundary times out.
+                let start = Arc::new(Mutex::new(view! { suspense-start(data-key=key.to_string()) }));
+                let (view, suspense_scope) = create_suspense_scope(move || HydrationRegistry::in_suspense_scope(key, move || call_unlisted(children)));
+                let no_ssr = view! { NoSsr {} };
+                let content = Arc::new(Mutex::new(view! {
                     Show(when=move || true) {
                         (view)
                     }
+                }));
+
+                let deadline = try_use_context::<SuspenseDeadline>();
+                let own_expired = create_signal(false);
+                if let Some(timeout) = timeout {
+                    sycamore_futures::spawn_local_scoped(async move {
+                        sycamore_futures::sleep(timeout).await;
+                        own_expired.set(true);
+                    });
+                }
+                let is_loading = suspense_scope.is_loading();
+                let fallback = std::rc::Rc::new(fallback);
+                create_effect({
+                    let start = start.clone();
+                    let content = content.clone();
+                    move || {
+                        let expired = own_expired.get() || deadline.is_some_and(|deadline| deadline.expired.get());
+                        if !expired || !is_loading.get() || untrack(|| suspense_scope.is_abandoned()) {
+                            return;
+                        }
+                        // Stop waiting for this boundary and render the fallback instead. The
+                        // `data-timeout` attribute tells the client that it needs to load the
+                        // boundary by itself.
+                        suspense_scope.abandon();
+                        if let Some(deadline) = deadline {
+                            deadline.timed_out.update(|keys| keys.push(key));
+                        }
+                        if let SsrNode::Element { attributes, .. } = &mut start.lock().unwrap().nodes[0] {
+                            attributes.push(("data-timeout".into(), "".into()));
+                        }
+                        let fallback = fallback.clone();
+                        let fallback = untrack(|| view! {
+                            NoHydrate {
+                                (fallback())
+                                suspense-end(data-key=key.to_string())
+                            }
+                        });
+                        *content.lock().unwrap() = fallback;
+                    }
+                });
+
+                let start = View::from(SsrNode::Dynamic { view: start });
+                let content = View::from(SsrNode::Dynamic { view: content });
+                view! {
+                    (start)
+                    (no_ssr)
+                    (content)
                 }
             },
             // In streaming mode, we render the fallback and then stream the result of the children
@@ -95,18 +182,24 @@ pub fn Suspense(props: SuspenseProps) -> View {
             SsrMode::Streaming => {
                 let key = use_suspense_key();
                 let start = view! { suspense-start(data-key=key.to_string()) };
-                let (view, suspense_scope) = create_suspense_scope(move || HydrationRegistry::in_suspense_scope(key, move || children.call()));
+                let (view, suspense_scope) = create_suspense_scope(move || HydrationRegistry::in_suspense_scope(key, move || call_unlisted(children)));
+                let entry = SuspenseListEntry::register(suspense_scope.is_loading(), suspense_scope.sent);
+                let show_fallback = entry.show_fallback();
 
                 let suspense_stream = use_context::<SuspenseStream>();
                 suspense_stream.futures.borrow_mut().push(async move {
                     suspense_scope.until_finished().await;
                     debug_assert!(!suspense_scope.sent.get(), "suspense scope should not yet be sent");
 
-                    // Make sure parent is sent first.
+                    // Make sure parent is sent first. If we are inside a `SuspenseList`, also make
+                    // sure that the fragments are sent in the right order.
                     let (tx, rx) = futures::channel::oneshot::channel();
                     let mut tx = Some(tx);
                     create_effect(move || {
-                        if !suspense_scope.sent.get() && suspense_scope.parent.as_ref().map_or(true, |parent| parent.get().sent.get()) {
+                        if !suspense_scope.sent.get()
+                            && suspense_scope.parent.as_ref().is_none_or(|parent| parent.get().sent.get())
+                            && entry.can_send()
+                        {
                             suspense_scope.sent.set(true);
                             tx.take().unwrap().send(()).unwrap();
                         }
@@ -122,13 +215,20 @@ pub fn Suspense(props: SuspenseProps) -> View {
                 view! {
                     NoSsr {}
                     (start)
-                    NoHydrate(children=Children::new(fallback))
+                    NoHydrate {
+                        Show(when=show_fallback) {
+                            (fallback())
+                        }
+                    }
                     (end)
                 }
             }
         }
     }
     is_not_ssr! {
+        // Timeouts are handled by the server.
+        let _ = timeout;
+
         let mode = if IS_HYDRATING.get() {
             use_context::<SsrMode>()
         } else {
@@ -136,18 +236,23 @@ pub fn Suspense(props: SuspenseProps) -> View {
         };
         match mode {
             SsrMode::Sync => {
-                let (view, suspense_scope) = create_suspense_scope(move || children.call());
+                let (view, suspense_scope) = create_suspense_scope(move || call_unlisted(children));
                 let is_loading = suspense_scope.is_loading();
+                let entry = SuspenseListEntry::register(is_loading, suspense_scope.sent);
+                // The server has already rendered the fallback so it should not be delayed.
+                let fallback_delay = if IS_HYDRATING.get() { Duration::ZERO } else { fallback_delay };
+                let show_fallback = create_delayed_fallback(entry.show_fallback(), fallback_delay, min_fallback_duration);
+                let revealed = keep_revealed(entry.revealed(), show_fallback);
 
                 create_effect(move || {
                     set_is_loading(is_loading.get());
                 });
 
                 view! {
-                    Show(when=is_loading) {
+                    Show(when=show_fallback) {
                         (fallback())
                     }
-                    Show(when=move || !is_loading.get()) {
+                    Show(when=revealed) {
                         (view)
                     }
                 }
@@ -165,18 +270,49 @@ pub fn Suspense(props: SuspenseProps) -> View {
                 let node = start.nodes[0].as_web_sys().unchecked_ref::<web_sys::Element>();
                 let key: NonZeroU32 = node.get_attribute("data-key").unwrap().parse().unwrap();
 
-                let (view, suspense_scope) = HydrationRegistry::in_suspense_scope(key, move || create_suspense_scope(move || children.call()));
+                // The server timed out while waiting for this boundary and rendered the fallback
+                // instead. Remove the server rendered fallback and load the boundary on the
+                // client side once it is mounted.
+                if node.has_attribute("data-timeout") {
+                    let end = document()
+                        .query_selector(&format!("suspense-end[data-key=\"{key}\"]"))
+                        .unwrap()
+                        .expect("timed out suspense boundary should have an end marker");
+                    let no_ssr = node.next_sibling().expect("missing `<no-ssr>` element");
+                    // Remove everything after the `<no-ssr>` element, which is hydrated below, up
+                    // to and including the end marker.
+                    let range = document().create_range().unwrap();
+                    range.set_start_after(&no_ssr).unwrap();
+                    range.set_end_after(&end).unwrap();
+                    range.delete_contents().unwrap();
+                    return view! {
+                        NoSsr {
+                            Suspense(
+                                fallback=fallback,
+                                set_is_loading=set_is_loading,
+                                fallback_delay=fallback_delay,
+                                min_fallback_duration=min_fallback_duration,
+                                children=children,
+                            )
+                        }
+                    };
+                }
+
+                let (view, suspense_scope) = HydrationRegistry::in_suspense_scope(key, move || create_suspense_scope(move || call_unlisted(children)));
                 let is_loading = suspense_scope.is_loading();
+                let entry = SuspenseListEntry::register(is_loading, suspense_scope.sent);
+                let show_fallback = create_delayed_fallback(entry.show_fallback(), fallback_delay, min_fallback_duration);
+                let revealed = keep_revealed(entry.revealed(), show_fallback);
 
                 create_effect(move || set_is_loading(is_loading.get()));
 
                 view! {
                     NoSsr {
-                        Show(when=move || is_loading.get()) {
+                        Show(when=show_fallback) {
                             (fallback())
                         }
                     }
-                    Show(when=move || !is_loading.get()) {
+                    Show(when=revealed) {
                         (view)
                     }
                 }
@@ -185,6 +321,300 @@ pub fn Suspense(props: SuspenseProps) -> View {
     }
 }
 
+/// Calls the children of a [`Suspense`] boundary.
+///
+/// Only direct child boundaries of a [`SuspenseList`] are coordinated by it, so the list is hidden
+/// from the children.
+fn call_unlisted(children: Children) -> View {
+    provide_context(SuspenseListContext(None));
+    children.call()
+}
+
+/// Delays showing a fallback by `delay` and keeps it shown for at least `min_duration` once it is
+/// visible.
+///
+/// Returns a signal that follows `show` with the delays applied. The timers use
+/// [`sleep`](sycamore_futures::sleep) and [`now`](sycamore_futures::now) from the current
+/// executor so that they can be controlled in tests with a `TestExecutor` from the `test-utils`
+/// feature of `sycamore-futures`. This is what powers the `fallback_delay` and
+/// `min_fallback_duration` props of [`Suspense`].
+pub fn create_delayed_fallback(
+    show: ReadSignal<bool>,
+    delay: Duration,
+    min_duration: Duration,
+) -> ReadSignal<bool> {
+    use sycamore_futures::{now, sleep, spawn_local_scoped};
+
+    if delay.is_zero() && min_duration.is_zero() {
+        return show;
+    }
+
+    let visible = create_signal(false);
+    let shown_at = create_signal(Duration::ZERO);
+    // Any pending timer from a previous run is aborted when the effect is run again.
+    create_effect(move || {
+        if show.get() {
+            if visible.get_untracked() {
+                return;
+            }
+            if delay.is_zero() {
+                shown_at.set(now());
+                visible.set(true);
+            } else {
+                spawn_local_scoped(async move {
+                    sleep(delay).await;
+                    shown_at.set(now());
+                    visible.set(true);
+                });
+            }
+        } else if visible.get_untracked() {
+            let elapsed = now().saturating_sub(shown_at.get_untracked());
+            if elapsed >= min_duration {
+                visible.set(false);
+            } else {
+                spawn_local_scoped(async move {
+                    sleep(min_duration - elapsed).await;
+                    visible.set(false);
+                });
+            }
+        }
+    });
+    *visible
+}
+
+/// Returns whether the content of a boundary should be shown.
+///
+/// Once the content has been revealed, it stays visible while the boundary is loading again until
+/// the (possibly delayed) fallback is shown, so that the boundary is not blank in the meantime.
+#[cfg_attr(any(not(target_arch = "wasm32"), sycamore_force_ssr), allow(dead_code))]
+fn keep_revealed(revealed: ReadSignal<bool>, show_fallback: ReadSignal<bool>) -> ReadSignal<bool> {
+    let was_revealed = std::cell::Cell::new(false);
+    create_selector(move || {
+        let revealed = revealed.get();
+        was_revealed.set(was_revealed.get() || revealed);
+        !show_fallback.get() && was_revealed.get()
+    })
+}
+
+/// The order in which the boundaries inside a [`SuspenseList`] are revealed.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum RevealOrder {
+    /// A boundary is only revealed once all the boundaries before it are revealed.
+    #[default]
+    Forwards,
+    /// A boundary is only revealed once all the boundaries after it are revealed.
+    Backwards,
+    /// All boundaries are revealed at the same time once all of them are resolved.
+    Together,
+}
+
+/// Which fallbacks are shown for the boundaries inside a [`SuspenseList`] that are not yet
+/// revealed.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SuspenseTail {
+    /// Show the fallbacks of all boundaries that are not yet revealed.
+    #[default]
+    Visible,
+    /// Only show the fallback of the next boundary that is to be revealed.
+    Collapsed,
+    /// Do not show any fallbacks.
+    Hidden,
+}
+
+/// Props for [`SuspenseList`].
+#[derive(Props)]
+pub struct SuspenseListProps {
+    /// The order in which the child boundaries are revealed.
+    #[prop(default)]
+    reveal_order: RevealOrder,
+    /// Which fallbacks are shown for the child boundaries that are not yet revealed.
+    #[prop(default)]
+    tail: SuspenseTail,
+    children: Children,
+}
+
+/// `SuspenseList` coordinates the order in which its child [`Suspense`] boundaries swap their
+/// fallbacks for their content. This prevents layout jumps when boundaries resolve in an
+/// arbitrary order.
+///
+/// Only the `Suspense` boundaries that are not nested inside another boundary are coordinated. In
+/// [`SsrMode::Streaming`], the resolved fragments are also sent in the reveal order.
+///
+/// # Example
+/// ```
+/// # use sycamore::prelude::*;
+/// # use sycamore::web::{RevealOrder, Suspense, SuspenseList, SuspenseTail};
+/// # #[component]
+/// # async fn Post() -> View { view! {} }
+/// #[component]
+/// fn Feed() -> View {
+///     view! {
+///         SuspenseList(reveal_order=RevealOrder::Forwards, tail=SuspenseTail::Collapsed) {
+///             Suspense(fallback=|| view! { "Loading..." }) { Post {} }
+///             Suspense(fallback=|| view! { "Loading..." }) { Post {} }
+///         }
+///     }
+/// }
+/// ```
+#[component]
+pub fn SuspenseList(props: SuspenseListProps) -> View {
+    let list = SuspenseListState {
+        entries: create_signal(Vec::new()),
+        next_id: create_signal(0),
+        reveal_order: props.reveal_order,
+        tail: props.tail,
+    };
+    provide_context_in_new_scope(SuspenseListContext(Some(list)), move || {
+        props.children.call()
+    })
+}
+
+/// Context for registering [`Suspense`] boundaries with the nearest [`SuspenseList`].
+///
+/// `Suspense` provides `None` for its children so that only direct child boundaries are
+/// coordinated.
+#[derive(Clone, Copy)]
+struct SuspenseListContext(Option<SuspenseListState>);
+
+/// The state of a [`SuspenseList`].
+#[derive(Clone, Copy)]
+struct SuspenseListState {
+    /// All the registered boundaries in document order. Boundaries are removed once they are
+    /// destroyed.
+    entries: Signal<Vec<SuspenseListItem>>,
+    /// The id of the next boundary that is registered.
+    next_id: Signal<usize>,
+    reveal_order: RevealOrder,
+    tail: SuspenseTail,
+}
+
+/// A [`Suspense`] boundary that is registered with a [`SuspenseList`].
+#[derive(Clone, Copy)]
+struct SuspenseListItem {
+    id: usize,
+    is_loading: ReadSignal<bool>,
+    /// Only used in [`SsrMode::Streaming`].
+    #[cfg_attr(all(target_arch = "wasm32", not(sycamore_force_ssr)), allow(dead_code))]
+    sent: Signal<bool>,
+}
+
+impl SuspenseListState {
+    /// Returns the index of the boundary with the given `id`, or `None` if it has been removed.
+    fn position(self, id: usize) -> Option<usize> {
+        self.entries
+            .with(|entries| entries.iter().position(|item| item.id == id))
+    }
+
+    /// Returns the indices of all the boundaries in the order in which they are revealed.
+    fn order(self) -> Vec<usize> {
+        let len = self.entries.with(Vec::len);
+        match self.reveal_order {
+            RevealOrder::Forwards | RevealOrder::Together => (0..len).collect(),
+            RevealOrder::Backwards => (0..len).rev().collect(),
+        }
+    }
+
+    fn is_revealed(self, index: usize) -> bool {
+        let is_ready = |item: &SuspenseListItem| !item.is_loading.get();
+        self.entries.with(|entries| match self.reveal_order {
+            RevealOrder::Forwards => entries[..=index].iter().all(is_ready),
+            RevealOrder::Backwards => entries[index..].iter().all(is_ready),
+            RevealOrder::Together => entries.iter().all(is_ready),
+        })
+    }
+
+    fn show_fallback(self, index: usize) -> bool {
+        if self.is_revealed(index) {
+            return false;
+        }
+        match self.tail {
+            SuspenseTail::Visible => true,
+            SuspenseTail::Collapsed => self
+                .order()
+                .into_iter()
+                .find(|&i| !self.is_revealed(i))
+                .is_some_and(|i| i == index),
+            SuspenseTail::Hidden => false,
+        }
+    }
+
+    /// Whether the boundary is revealed and the boundary before it in the reveal order has already
+    /// been sent. Used in [`SsrMode::Streaming`].
+    #[cfg_ssr]
+    fn can_send(self, index: usize) -> bool {
+        if !self.is_revealed(index) {
+            return false;
+        }
+        let order = self.order();
+        let pos = order.iter().position(|&i| i == index).unwrap();
+        pos.checked_sub(1)
+            .is_none_or(|prev| self.entries.with(|entries| entries[order[prev]].sent.get()))
+    }
+}
+
+/// The registration of a [`Suspense`] boundary with the nearest [`SuspenseList`], if any.
+#[derive(Clone, Copy)]
+struct SuspenseListEntry {
+    /// The list and the id of the boundary in it.
+    list: Option<(SuspenseListState, usize)>,
+    is_loading: ReadSignal<bool>,
+}
+
+impl SuspenseListEntry {
+    /// Registers the current boundary with the nearest [`SuspenseList`]. The boundary is
+    /// unregistered when the current scope is destroyed.
+    fn register(is_loading: ReadSignal<bool>, sent: Signal<bool>) -> Self {
+        let list = try_use_context::<SuspenseListContext>().and_then(|cx| cx.0);
+        let list = list.map(|list| {
+            let id = list.next_id.get_untracked();
+            list.next_id.set_silent(id + 1);
+            list.entries.update(|entries| {
+                entries.push(SuspenseListItem {
+                    id,
+                    is_loading,
+                    sent,
+                });
+            });
+            on_cleanup(move || {
+                if list.entries.is_alive() {
+                    list.entries
+                        .update(|entries| entries.retain(|item| item.id != id));
+                }
+            });
+            (list, id)
+        });
+        Self { list, is_loading }
+    }
+
+    /// Whether the content of the boundary should be shown.
+    #[cfg_not_ssr]
+    fn revealed(self) -> ReadSignal<bool> {
+        create_selector(move || match self.list {
+            Some((list, id)) => list
+                .position(id)
+                .is_none_or(|index| list.is_revealed(index)),
+            None => !self.is_loading.get(),
+        })
+    }
+
+    /// Whether the fallback of the boundary should be shown.
+    fn show_fallback(self) -> ReadSignal<bool> {
+        create_selector(move || match self.list {
+            Some((list, id)) => list
+                .position(id)
+                .is_some_and(|index| list.show_fallback(index)),
+            None => self.is_loading.get(),
+        })
+    }
+
+    /// Whether the boundary can be sent in [`SsrMode::Streaming`] mode.
+    #[cfg_ssr]
+    fn can_send(self) -> bool {
+        self.list
+            .is_none_or(|(list, id)| list.position(id).is_none_or(|index| list.can_send(index)))
+    }
+}
+
 /// `Transition` is like [`Suspense`] except that it keeps the previous content visible until the
 /// new content is ready.
 #[component]
@@ -198,7 +628,15 @@ pub fn Transition(props: SuspenseProps) -> View {
 
         // We create a detached suspense scope here to not create a deadlock with the outer
         // suspense.
+        let outer = try_use_context::<SuspenseScope>();
         let (children, scope) = create_detached_suspense_scope(move || children.call());
+        // The detached scope is not abandoned along with the outer scope, so do it manually.
+        // Otherwise a timed out transition would keep the SSR render waiting.
+        create_effect(move || {
+            if outer.is_some_and(|outer| outer.is_abandoned()) {
+                scope.abandon();
+            }
+        });
         // Trigger the outer suspense scope. Note that this is only triggered on the initial render
         // and future renders will be captured by the inner suspense scope.
         create_suspense_task(scope.until_finished());
@@ -213,17 +651,151 @@ pub fn Transition(props: SuspenseProps) -> View {
         }
     }
 
-    view! {
-        Suspense(fallback=props.fallback, children=Children::new(move || {
+    // The props are passed on as a whole so that optional props such as `timeout` are kept.
+    Suspense(SuspenseProps {
+        children: Children::new(move || {
             view! { TransitionInner(children=props.children, set_is_loading=props.set_is_loading) }
-        }))
+        }),
+        set_is_loading: Box::new(|_| {}),
+        ..props
+    })
+}
+
+/// Props for [`Await`].
+#[derive(Props)]
+pub struct AwaitProps<T: 'static, Fut: Future<Output = T> + 'static> {
+    /// The future to await.
+    future: Fut,
+    /// Renders the output of the future once it has resolved.
+    #[prop(setter(transform = |f: impl FnOnce(T) -> View + 'static| Box::new(f) as Box<dyn FnOnce(T) -> View>))]
+    then: Box<dyn FnOnce(T) -> View>,
+    /// If set, the future is awaited inside of its own [`Suspense`] boundary with this fallback.
+    /// Otherwise, the nearest `Suspense` boundary is suspended.
+    #[prop(default, setter(transform = |f: impl Fn() -> View + 'static| Some(Box::new(f) as Box<dyn Fn() -> View>)))]
+    fallback: Option<Box<dyn Fn() -> View>>,
+}
+
+/// `Await` renders the output of a future inline. This is like an async component without having
+/// to define one.
+///
+/// The future is run on both client and server, like an async component. By default, it suspends
+/// the nearest [`Suspense`] boundary. If `fallback` is set, the future is awaited inside of its own
+/// boundary instead.
+///
+/// For futures that can fail, see [`TryAwait`].
+///
+/// # Example
+/// ```
+/// # use sycamore::prelude::*;
+/// # use sycamore::web::Await;
+/// # async fn fetch_user_name() -> String { String::new() }
+/// #[component]
+/// fn Greeting() -> View {
+///     view! {
+///         Await(
+///             future=fetch_user_name(),
+///             then=|name| view! { "Hello, " (name) "!" },
+///             fallback=|| view! { "Loading..." },
+///         )
+///     }
+/// }
+/// ```
+#[component]
+pub fn Await<T: 'static, Fut: Future<Output = T> + 'static>(props: AwaitProps<T, Fut>) -> View {
+    let AwaitProps {
+        future,
+        then,
+        fallback,
+    } = props;
+
+    let content = move || WrapAsync(move || async move { then(future.await) });
+    match fallback {
+        Some(fallback) => view! {
+            Suspense(fallback=fallback, children=Children::new(content))
+        },
+        None => content(),
     }
 }
 
+/// Props for [`TryAwait`].
+#[derive(Props)]
+pub struct TryAwaitProps<T: 'static, E: 'static, Fut: Future<Output = Result<T, E>> + 'static> {
+    /// The future to await.
+    future: Fut,
+    /// Renders the output of the future if it resolved successfully.
+    #[prop(setter(transform = |f: impl FnOnce(T) -> View + 'static| Box::new(f) as Box<dyn FnOnce(T) -> View>))]
+    then: Box<dyn FnOnce(T) -> View>,
+    /// Renders the error if the future failed. By default, nothing is rendered.
+    #[prop(default = Box::new(|_| view! {}), setter(transform = |f: impl FnOnce(E) -> View + 'static| Box::new(f) as Box<dyn FnOnce(E) -> View>))]
+    error: Box<dyn FnOnce(E) -> View>,
+    /// If set, the future is awaited inside of its own [`Suspense`] boundary with this fallback.
+    /// Otherwise, the nearest `Suspense` boundary is suspended.
+    #[prop(default, setter(transform = |f: impl Fn() -> View + 'static| Some(Box::new(f) as Box<dyn Fn() -> View>)))]
+    fallback: Option<Box<dyn Fn() -> View>>,
+}
+
+/// Like [`Await`] but for futures that return a [`Result`]. If the future fails, the `error` slot
+/// is rendered instead of `then`.
+///
+/// # Example
+/// ```
+/// # use sycamore::prelude::*;
+/// # use sycamore::web::TryAwait;
+/// # async fn fetch_user_name() -> Result<String, String> { Ok(String::new()) }
+/// #[component]
+/// fn Greeting() -> View {
+///     view! {
+///         TryAwait(
+///             future=fetch_user_name(),
+///             then=|name| view! { "Hello, " (name) "!" },
+///             error=|err| view! { "Could not load user: " (err) },
+///             fallback=|| view! { "Loading..." },
+///         )
+///     }
+/// }
+/// ```
+#[component]
+pub fn TryAwait<T: 'static, E: 'static, Fut: Future<Output = Result<T, E>> + 'static>(
+    props: TryAwaitProps<T, E, Fut>,
+) -> View {
+    let TryAwaitProps {
+        future,
+        then,
+        error,
+        fallback,
+    } = props;
+
+    Await(AwaitProps {
+        future,
+        then: Box::new(move |result| match result {
+            Ok(value) => then(value),
+            Err(err) => error(err),
+        }),
+        fallback,
+    })
+}
+
 /// Convert an async component to a regular sync component. Also wraps the async component inside a
 /// suspense scope so that content is properly suspended.
 #[component]
 pub fn WrapAsync<F: Future<Output = View>>(f: impl FnOnce() -> F + 'static) -> View {
+    wrap_async(None, f)
+}
+
+/// Like [`WrapAsync`] but labels the suspense task with the path of the async component. This is
+/// used by the `#[component]` macro.
+#[doc(hidden)]
+pub fn wrap_async_named<F: Future<Output = View>>(
+    name: &'static str,
+    f: impl FnOnce() -> F + 'static,
+) -> View {
+    wrap_async(Some(name), f)
+}
+
+fn wrap_async<F: Future<Output = View>>(
+    name: Option<&'static str>,
+    f: impl FnOnce() -> F + 'static,
+) -> View {
     is_not_ssr! {
         let mode = if IS_HYDRATING.get() {
             use_context::<SsrMode>()
@@ -237,21 +809,21 @@ pub fn WrapAsync<F: Future<Output = View>>(f: impl FnOnce() -> F + 'static) -> V
                     view.track();
                     view.update_silent(std::mem::take)
                 }) };
-                create_suspense_task(async move {
+                create_async_component_task(name, async move {
                     view.set(f().await);
                 });
                 ret
             }
             SsrMode::Blocking | SsrMode::Streaming => {
                 // TODO: This does not properly hydrate dynamic text nodes.
-                create_suspense_task(async move { f().await; });
+                create_async_component_task(name, async move { f().await; });
                 view! {}
             }
         }
     }
     is_ssr! {
         let node = create_signal(View::default());
-        create_suspense_task(async move {
+        create_async_component_task(name, async move {
             node.set(f().await);
         });
         View::from_dynamic(move || {
@@ -261,6 +833,14 @@ pub fn WrapAsync<F: Future<Output = View>>(f: impl FnOnce() -> F + 'static) -> V
     }
 }
 
+/// Creates the suspense task of an async component, labeled with its name if it is known.
+fn create_async_component_task(name: Option<&'static str>, f: impl Future<Output = ()> + 'static) {
+    match name {
+        Some(name) => create_labeled_suspense_task(name, f),
+        None => create_suspense_task(f),
+    }
+}
+
 /// Represents a streamed suspense view fragment.
 #[cfg_ssr]
 pub(crate) struct SuspenseFragment {
@@ -288,26 +868,228 @@ pub(crate) struct SuspenseStream {
     >,
 }
 
-/// Global counter for providing suspense key.
-#[derive(Debug, Clone, Copy)]
-struct SuspenseCounter {
-    next: Signal<NonZeroU32>,
+/// Context for the deadline of [`render_to_string_await_suspense_with_timeout`].
+#[cfg_ssr]
+#[derive(Clone, Copy)]
+pub(crate) struct SuspenseDeadline {
+    /// Set to `true` once the deadline has passed.
+    pub expired: Signal<bool>,
+    /// The keys of all the boundaries that timed out.
+    pub timed_out: Signal<Vec<NonZeroU32>>,
 }
 
-impl SuspenseCounter {
-    fn new() -> Self {
-        Self {
-            next: create_signal(NonZeroU32::new(1).unwrap()),
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Creates a list with a boundary for each of the loading signals.
+    fn create_list(
+        reveal_order: RevealOrder,
+        tail: SuspenseTail,
+        is_loading: &[Signal<bool>],
+    ) -> SuspenseListState {
+        let list = SuspenseListState {
+            entries: create_signal(Vec::new()),
+            next_id: create_signal(is_loading.len()),
+            reveal_order,
+            tail,
+        };
+        list.entries.set(
+            is_loading
+                .iter()
+                .enumerate()
+                .map(|(id, is_loading)| SuspenseListItem {
+                    id,
+                    is_loading: **is_loading,
+                    sent: create_signal(false),
+                })
+                .collect(),
+        );
+        list
+    }
+
+    fn revealed(list: SuspenseListState) -> Vec<bool> {
+        (0..list.entries.with(Vec::len))
+            .map(|i| list.is_revealed(i))
+            .collect()
+    }
+
+    fn fallbacks(list: SuspenseListState) -> Vec<bool> {
+        (0..list.entries.with(Vec::len))
+            .map(|i| list.show_fallback(i))
+            .collect()
+    }
+
+    #[test]
+    fn delayed_fallback() {
+        use sycamore_futures::with_test_executor;
+
+        let ms = Duration::from_millis;
+        with_test_executor(|executor| {
+            let show = create_signal(true);
+            let visible = create_delayed_fallback(*show, ms(100), ms(300));
+            assert!(!visible.get());
+
+            // Fast loads never show the fallback.
+            executor.advance_time(ms(50));
+            show.set(false);
+            executor.advance_time(ms(100));
+            assert!(!visible.get());
+
+            // Slow loads show the fallback after the delay...
+            show.set(true);
+            executor.advance_time(ms(99));
+            assert!(!visible.get());
+            executor.advance_time(ms(1));
+            assert!(visible.get());
+
+            // ...and keep it visible for at least the minimum duration.
+            executor.advance_time(ms(100));
+            show.set(false);
+            assert!(visible.get());
+            executor.advance_time(ms(199));
+            assert!(visible.get());
+            executor.advance_time(ms(1));
+            assert!(!visible.get());
+        });
+    }
+
+    #[test]
+    fn pending_tasks_are_labeled_with_component_name() {
+        use sycamore_futures::{sleep, with_test_executor};
+
+        #[component]
+        async fn Delayed() -> View {
+            sleep(Duration::from_secs(1)).await;
+            view! {}
         }
+
+        with_test_executor(|_| {
+            provide_context(HydrationRegistry::new());
+            provide_context(SsrMode::Blocking);
+
+            let (_, scope) = create_suspense_scope(|| view! { Delayed {} });
+            let tasks = scope.pending_tasks().get_clone();
+            assert_eq!(tasks.len(), 1);
+            assert_eq!(
+                tasks[0].label.as_deref(),
+                Some("sycamore_web::suspense::tests::Delayed")
+            );
+        });
     }
-}
 
-/// Get the next suspense key.
-pub fn use_suspense_key() -> NonZeroU32 {
-    let global_scope = use_global_scope();
-    let counter = global_scope.run_in(|| use_context_or_else(SuspenseCounter::new));
+    #[test]
+    fn previous_content_is_kept_while_fallback_is_delayed() {
+        let _ = create_root(|| {
+            let revealed = create_signal(false);
+            let show_fallback = create_signal(false);
+            let visible = keep_revealed(*revealed, *show_fallback);
+            // Nothing to show on the initial load.
+            assert!(!visible.get());
 
-    let next = counter.next.get();
-    counter.next.set(next.checked_add(1).unwrap());
-    next
+            revealed.set(true);
+            assert!(visible.get());
+
+            // Loading again keeps the content until the fallback is shown.
+            revealed.set(false);
+            assert!(visible.get());
+            show_fallback.set(true);
+            assert!(!visible.get());
+
+            show_fallback.set(false);
+            revealed.set(true);
+            assert!(visible.get());
+        });
+    }
+
+    #[test]
+    fn reveal_order() {
+        let _ = create_root(|| {
+            let is_loading = [
+                create_signal(true),
+                create_signal(false),
+                create_signal(true),
+            ];
+
+            let forwards = create_list(RevealOrder::Forwards, SuspenseTail::Visible, &is_loading);
+            let backwards = create_list(RevealOrder::Backwards, SuspenseTail::Visible, &is_loading);
+            let together = create_list(RevealOrder::Together, SuspenseTail::Visible, &is_loading);
+            assert_eq!(revealed(forwards), [false, false, false]);
+            assert_eq!(revealed(backwards), [false, false, false]);
+
+            is_loading[2].set(false);
+            assert_eq!(revealed(forwards), [false, false, false]);
+            assert_eq!(revealed(backwards), [false, true, true]);
+            assert_eq!(revealed(together), [false, false, false]);
+
+            is_loading[0].set(false);
+            assert_eq!(revealed(forwards), [true, true, true]);
+            assert_eq!(revealed(together), [true, true, true]);
+        });
+    }
+
+    #[test]
+    fn tail() {
+        let _ = create_root(|| {
+            let is_loading = [
+                create_signal(false),
+                create_signal(true),
+                create_signal(true),
+            ];
+
+            let visible = create_list(RevealOrder::Forwards, SuspenseTail::Visible, &is_loading);
+            let collapsed =
+                create_list(RevealOrder::Forwards, SuspenseTail::Collapsed, &is_loading);
+            let hidden = create_list(RevealOrder::Forwards, SuspenseTail::Hidden, &is_loading);
+            assert_eq!(fallbacks(visible), [false, true, true]);
+            assert_eq!(fallbacks(collapsed), [false, true, false]);
+            assert_eq!(fallbacks(hidden), [false, false, false]);
+
+            let collapsed =
+                create_list(RevealOrder::Backwards, SuspenseTail::Collapsed, &is_loading);
+            assert_eq!(fallbacks(collapsed), [false, false, true]);
+        });
+    }
+
+    #[test]
+    fn removed_boundaries_are_skipped() {
+        let _ = create_root(|| {
+            let is_loading = [create_signal(true), create_signal(false)];
+            let list = create_list(RevealOrder::Forwards, SuspenseTail::Visible, &is_loading);
+            assert_eq!(revealed(list), [false, false]);
+
+            list.entries.update(|entries| {
+                entries.remove(0);
+            });
+            assert_eq!(revealed(list), [true]);
+            assert_eq!(list.position(0), None);
+            assert_eq!(list.position(1), Some(0));
+        });
+    }
+
+    #[test]
+    fn entries_are_removed_on_cleanup() {
+        let _ = create_root(|| {
+            let list = create_list(RevealOrder::Forwards, SuspenseTail::Visible, &[]);
+            let show = create_signal(true);
+            create_effect(move || {
+                if show.get() {
+                    provide_context(SuspenseListContext(Some(list)));
+                    for _ in 0..3 {
+                        let _ = SuspenseListEntry::register(
+                            *create_signal(false),
+                            create_signal(false),
+                        );
+                    }
+                }
+            });
+            assert_eq!(list.entries.with(Vec::len), 3);
+
+            // Re-rendering the boundaries does not grow the list.
+            show.set(false);
+            assert_eq!(list.entries.with(Vec::len), 0);
+            show.set(true);
+            assert_eq!(list.entries.with(Vec::len), 3);
+        });
+    }
 }
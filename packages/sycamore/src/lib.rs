@@ -10,9 +10,15 @@
 //! - `hydrate` - Enables hydration support in DOM nodes. By default, hydration is disabled to
 //!   reduce binary size.
 //!
+//! - `islands` - Enables islands, i.e. components that are hydrated on their own while the rest
+//!   of the page stays static. Also enables `hydrate`.
+//!
 //! - `serde` - Enables serializing and deserializing `Signal`s and other wrapper types using
 //!   `serde`.
 //!
+//! - `server-state` - Enables passing state from the server to the client with
+//!   `provide_server_state` and `use_server_state`.
+//!
 //! - `suspense` - Enables suspense and resources. Also enables wrappers around
 //!   `wasm-bindgen-futures` to make it easier to extend a reactive scope into an `async` function.
 //!
@@ -70,10 +76,14 @@ pub mod futures {
 #[cfg(feature = "hydrate")]
 pub use sycamore_web::{hydrate, hydrate_in_scope, hydrate_to};
 pub use sycamore_web::{
-    render, render_in_scope, render_to, render_to_string, render_to_string_in_scope,
+    render, render_document, render_in_scope, render_to, render_to_string,
+    render_to_string_in_scope, render_to_writer,
 };
 #[cfg(feature = "suspense")]
-pub use sycamore_web::{render_to_string_await_suspense, render_to_string_stream};
+pub use sycamore_web::{
+    render_document_await_suspense, render_document_stream, render_to_string_await_suspense,
+    render_to_string_stream,
+};
 
 /// The Sycamore prelude.
 ///
@@ -104,7 +114,9 @@ pub mod prelude {
 /// Re-exports for use by `sycamore-macro`. Not intended for use by end-users.
 #[doc(hidden)]
 pub mod rt {
-    pub use sycamore_core::{component_scope, element_like_component_builder, Component, Props};
+    pub use sycamore_core::{
+        component_scope, element_like_component_builder, named_component_scope, Component, Props,
+    };
     #[cfg(feature = "suspense")]
     pub use sycamore_futures::*;
     pub use sycamore_macro::*;
@@ -0,0 +1,88 @@
+use sycamore::web::events::set_delegation;
+
+use super::*;
+
+fn click(element: &Element) {
+    let init = web_sys::EventInit::new();
+    init.set_bubbles(true);
+    let event = Event::new_with_event_init_dict("click", &init).unwrap();
+    element.dispatch_event(&event).unwrap();
+}
+
+#[wasm_bindgen_test]
+fn delegated_events_bubble() {
+    set_delegation(true);
+    let _ = create_root(|| {
+        let calls = create_signal(Vec::new());
+        let node = view! {
+            div(on:click=move |ev: web_sys::MouseEvent| {
+                let current_target = ev.current_target().unwrap();
+                assert_eq!(current_target.unchecked_into::<Element>().tag_name(), "DIV");
+                calls.update(|calls| calls.push("div"));
+            }) {
+                button(id="inner", on:click=move |_| calls.update(|calls| calls.push("inner"))) {}
+                button(id="stop", on:click|stop=move |_| calls.update(|calls| calls.push("stop"))) {}
+            }
+        };
+        sycamore::render_in_scope(|| node, &test_container());
+
+        click(&query("#inner"));
+        assert_eq!(calls.get_clone(), vec!["inner", "div"]);
+
+        calls.set(Vec::new());
+        click(&query("#stop"));
+        assert_eq!(calls.get_clone(), vec!["stop"]);
+    });
+    set_delegation(false);
+}
+
+#[wasm_bindgen_test]
+fn delegated_events_bubble_from_removed_node() {
+    set_delegation(true);
+    let _ = create_root(|| {
+        let calls = create_signal(Vec::new());
+        let node = view! {
+            div(on:click=move |_| calls.update(|calls| calls.push("div"))) {
+                button(on:click=move |ev: web_sys::MouseEvent| {
+                    ev.current_target().unwrap().unchecked_into::<Element>().remove();
+                    calls.update(|calls| calls.push("button"));
+                }) {}
+            }
+        };
+        sycamore::render_in_scope(|| node, &test_container());
+
+        click(&query("button"));
+        assert_eq!(calls.get_clone(), vec!["button", "div"]);
+    });
+    set_delegation(false);
+}
+
+#[wasm_bindgen_test]
+fn delegated_events_are_removed_on_cleanup() {
+    set_delegation(true);
+    let _ = create_root(|| {
+        let show = create_signal(true);
+        let count = create_signal(0);
+        let node = view! {
+            div(id="container") {
+                (if show.get() {
+                    view! { button(on:click=move |_| count.set(count.get() + 1)) {} }
+                } else {
+                    view! {}
+                })
+            }
+        };
+        sycamore::render_in_scope(|| node, &test_container());
+
+        let button = query("button");
+        click(&button);
+        assert_eq!(count.get(), 1);
+
+        show.set(false);
+        // Re-attach the button to check that the handler was really removed.
+        query("#container").append_child(&button).unwrap();
+        click(&button);
+        assert_eq!(count.get(), 1);
+    });
+    set_delegation(false);
+}
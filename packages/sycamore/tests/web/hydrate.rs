@@ -195,3 +195,255 @@ mod top_level_dynamic_with_siblings {
         });
     }
 }
+
+mod mismatch_recovery {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use sycamore::web::{
+        set_hydration_mismatch_handler, set_hydration_mismatch_recovery, HydrationMismatch,
+    };
+
+    use super::*;
+
+    fn v() -> View {
+        div().children(p().children("Client")).into()
+    }
+
+    /// Hydrates `v` into `html` and returns the reported mismatches.
+    fn hydrate_with_recovery(v: fn() -> View, c: &Element, html: &str) -> Vec<HydrationMismatch> {
+        c.set_inner_html(html);
+        let mismatches = Rc::new(RefCell::new(Vec::new()));
+        set_hydration_mismatch_handler({
+            let mismatches = mismatches.clone();
+            move |mismatch| mismatches.borrow_mut().push(mismatch.clone())
+        });
+        set_hydration_mismatch_recovery(true);
+
+        sycamore::hydrate_to(v, c);
+
+        set_hydration_mismatch_recovery(false);
+        mismatches.take()
+    }
+
+    #[wasm_bindgen_test]
+    fn wrong_tag() {
+        let c = test_container();
+        let mismatches = hydrate_with_recovery(
+            v,
+            &c,
+            r#"<div data-hk="0.0"><span data-hk="0.1">Server</span></div>"#,
+        );
+
+        assert_eq!(
+            mismatches
+                .iter()
+                .map(|mismatch| mismatch.to_string())
+                .collect::<Vec<_>>(),
+            ["hydration mismatch at `0.1`: expected <p>, found <span>"]
+        );
+        assert_eq!(
+            c.inner_html(),
+            r#"<div data-hk="0.0" data-hydrated=""><p>Client</p></div>"#
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn missing_element() {
+        let c = test_container();
+        let mismatches = hydrate_with_recovery(v, &c, r#"<div data-hk="0.0"></div>"#);
+
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].expected, "<p>");
+        assert_eq!(mismatches[0].found, "nothing");
+        assert_text_content!(query("div>p"), "Client");
+    }
+
+    #[wasm_bindgen_test]
+    fn missing_element_is_reported_once() {
+        let c = test_container();
+        let mismatches = hydrate_with_recovery(
+            || {
+                div()
+                    .children(p().children(span().children(strong().children("Client"))))
+                    .into()
+            },
+            &c,
+            r#"<div data-hk="0.0"></div>"#,
+        );
+
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].expected, "<p>");
+        assert_text_content!(query("div>p>span>strong"), "Client");
+    }
+
+    #[wasm_bindgen_test]
+    fn wrong_text() {
+        let c = test_container();
+        let mismatches = hydrate_with_recovery(
+            || p().children("Client").into(),
+            &c,
+            r#"<p data-hk="0.0">Server</p>"#,
+        );
+
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(
+            mismatches[0].to_string(),
+            "hydration mismatch at `0.0`: expected text \"Client\", found text \"Server\""
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn text_is_compared_exactly() {
+        let c = test_container();
+        let mismatches = hydrate_with_recovery(
+            || p().children("Client").into(),
+            &c,
+            r#"<p data-hk="0.0">Not the Client</p>"#,
+        );
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].found, "text \"Not the Client\"");
+
+        // Adjacent static text is merged into a single text node by the browser.
+        let mismatches = hydrate_with_recovery(
+            || p().children(("Hello, ", "Client")).into(),
+            &c,
+            r#"<p data-hk="0.0">Hello, Client</p>"#,
+        );
+        assert!(mismatches.is_empty());
+    }
+}
+
+mod lazy_hydrate_on_interaction {
+    use sycamore::web::{HydrationStrategy, LazyHydrate};
+
+    use super::*;
+
+    fn v(count: Signal<i32>) -> View {
+        view! {
+            LazyHydrate(strategy=HydrationStrategy::Interaction) {
+                button(on:click=move |_| count.set(count.get() + 1)) { "Click" }
+            }
+        }
+    }
+    static EXPECT: Expect = expect![[
+        r#"<sycamore-lazy data-key="1" style="display:contents" data-hk="0.0"><button data-hk="1.0">Click</button></sycamore-lazy>"#
+    ]];
+    #[test]
+    fn ssr() {
+        check(|| v(create_signal(0)), &EXPECT);
+    }
+    #[wasm_bindgen_test]
+    fn test() {
+        let c = test_container();
+        c.set_inner_html(EXPECT.data());
+
+        let _ = create_root(|| {
+            let count = create_signal(0);
+
+            sycamore::hydrate_in_scope(|| v(count), &c);
+
+            // The button is only hydrated once it is clicked.
+            let button = query_into::<HtmlElement>("button");
+            assert!(!button.has_attribute("data-hydrated"));
+
+            // The click that triggers hydration should still reach the handler.
+            button.click();
+            assert!(button.has_attribute("data-hydrated"));
+            assert_eq!(count.get(), 1);
+        });
+    }
+}
+
+mod cache {
+    use sycamore::web::Cache;
+
+    use super::*;
+
+    fn v(count: Signal<i32>) -> View {
+        view! {
+            p { "Before" }
+            Cache(key="hydrate-cache") {
+                button(on:click=move |_| count.set(count.get() + 1)) { "Click" }
+            }
+        }
+    }
+    static EXPECT: Expect = expect![[
+        r#"<p data-hk="0.0">Before</p><sycamore-cache data-key="4027719043" style="display:contents" data-hk="0.1"><button data-hk="4027719043.0">Click</button></sycamore-cache>"#
+    ]];
+    #[test]
+    fn ssr() {
+        // The second render uses the cached HTML.
+        check(|| v(create_signal(0)), &EXPECT);
+        check(|| v(create_signal(0)), &EXPECT);
+    }
+    #[wasm_bindgen_test]
+    fn test() {
+        let c = test_container();
+        c.set_inner_html(EXPECT.data());
+
+        let _ = create_root(|| {
+            let count = create_signal(0);
+
+            sycamore::hydrate_in_scope(|| v(count), &c);
+
+            let button = query_into::<HtmlElement>("button");
+            assert!(button.has_attribute("data-hydrated"));
+            button.click();
+            assert_eq!(count.get(), 1);
+        });
+    }
+}
+
+#[cfg(feature = "suspense")]
+mod suspense_timeout {
+    use std::time::Duration;
+
+    use sycamore::web::js_sys::Reflect;
+    use sycamore::web::{render_to_string_await_suspense_with_timeout, Suspense};
+
+    use super::*;
+
+    #[component]
+    async fn Content() -> View {
+        // Simulates a slow request that only times out on the server.
+        if is_ssr!() {
+            std::future::pending::<()>().await;
+        }
+        view! { p { "Content" } }
+    }
+
+    fn v() -> View {
+        view! {
+            Suspense(fallback=|| view! { p { "Fallback" } }) {
+                Content {}
+            }
+        }
+    }
+    static EXPECT: Expect = expect![[
+        r#"<suspense-start data-key="1" data-timeout="" data-hk="0.0"></suspense-start><no-ssr data-hk="0.1"></no-ssr><!--/--><p>Fallback</p><!--/--><suspense-end data-key="1"></suspense-end>"#
+    ]];
+    #[cfg(not(target_arch = "wasm32"))]
+    #[tokio::test]
+    async fn ssr() {
+        let output =
+            render_to_string_await_suspense_with_timeout(v, Duration::from_millis(10)).await;
+        EXPECT.assert_eq(&output.html);
+    }
+    #[wasm_bindgen_test]
+    async fn test() {
+        let c = test_container();
+        c.set_inner_html(EXPECT.data());
+        Reflect::set(&window(), &"__sycamore_ssr_mode".into(), &"blocking".into()).unwrap();
+
+        let _ = create_root(|| sycamore::hydrate_in_scope(v, &c));
+        Reflect::delete_property(&window(), &"__sycamore_ssr_mode".into()).unwrap();
+
+        // The server rendered fallback is removed and the boundary is loaded on the client.
+        assert!(!c.inner_html().contains("Fallback"));
+        assert!(c.query_selector("suspense-end").unwrap().is_none());
+        // Wait for the async component to resolve.
+        sycamore::futures::sleep(Duration::from_millis(10)).await;
+        assert_text_content!(query("p"), "Content");
+    }
+}
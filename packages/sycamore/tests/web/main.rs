@@ -1,4 +1,5 @@
 pub mod cleanup;
+pub mod delegation;
 pub mod hydrate;
 pub mod indexed;
 pub mod keyed;